@@ -52,12 +52,22 @@ impl Context {
         }
     }
 
+    /// Incremented at the start of each search. Callers can tag their own per-cell data with
+    /// this value to tell whether it was written during the most recent search.
+    pub fn generation(&self) -> u64 {
+        self.seen_set.generation()
+    }
+
+    /// The number of searches that can run before the generation wraps, at which point every
+    /// cell is reset.
+    pub fn generation_remaining(&self) -> u64 {
+        self.seen_set.generation_remaining()
+    }
+
     fn consider<B: BestSearch>(&mut self, best_search: &mut B, step: Step, depth: Depth) {
         if let Some(Visit) = self.seen_set.try_visit_step(step, depth) {
-            if best_search.can_step_updating_best(step) {
-                if !best_search.is_at_max_depth(depth) {
-                    self.queue.push_back(Node { step, depth });
-                }
+            if best_search.can_step_updating_best(step) && !best_search.is_at_max_depth(depth) {
+                self.queue.push_back(Node { step, depth });
             }
         }
     }
@@ -90,12 +100,20 @@ impl Context {
     }
 
     pub fn best_search_path<B: BestSearch>(&mut self, mut best_search: B, start: Coord, path: &mut Path) {
+        if !self.seen_set.size().is_valid(start) {
+            path.clear();
+            return;
+        }
         self.best_search_core(&mut best_search, start);
         let end = best_search.best_coord().unwrap_or(start);
         self.seen_set.build_path_to(end, path);
     }
 
+    #[must_use]
     pub fn best_search_first<B: BestSearch>(&mut self, mut best_search: B, start: Coord) -> Option<CardinalDirection> {
+        if !self.seen_set.size().is_valid(start) {
+            return None;
+        }
         self.best_search_core(&mut best_search, start);
         let end = best_search.best_coord().unwrap_or(start);
         self.seen_set.first_direction_towards(end)
@@ -309,6 +327,31 @@ mod test {
         assert_eq!(path.len(), 0);
     }
 
+    #[test]
+    fn generation() {
+        let Test { grid, start } = str_slice_to_test(GRID_A);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        let generation = ctx.generation();
+        ctx.best_search_path(ConstrainedSearch::new(100, &grid), start, &mut path);
+        assert_eq!(ctx.generation(), generation + 1);
+        let _ = ctx.best_search_first(ConstrainedSearch::new(100, &grid), start);
+        assert_eq!(ctx.generation(), generation + 2);
+    }
+
+    #[test]
+    fn start_outside_grid() {
+        let grid = Grid::new_clone(Size::new(0, 0), Cell::Traversable(0));
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.best_search_path(ConstrainedSearch::new(100, &grid), Coord::new(0, 0), &mut path);
+        assert_eq!(path.len(), 0);
+        assert_eq!(
+            ctx.best_search_first(ConstrainedSearch::new(100, &grid), Coord::new(0, 0)),
+            None
+        );
+    }
+
     #[test]
     fn grid_random() {
         let mut rng = Isaac64Rng::seed_from_u64(0);
@@ -1,5 +1,6 @@
+use crate::coord::UNIT_COORDS;
 use crate::step::Step;
-use grid_2d::Coord;
+use grid_2d::{Coord, Size};
 
 pub trait CanEnter {
     fn can_enter(&self, coord: Coord) -> bool;
@@ -7,3 +8,69 @@ pub trait CanEnter {
         self.can_enter(step.to_coord)
     }
 }
+
+/// Treats a cell as enterable only if it lies in some 2x2 block of cells that can all be
+/// entered. This keeps searches out of single-width gaps while still allowing 2-wide
+/// corridors, and is a cheap approximation of an agent larger than one cell. Edge rules
+/// expressed through the wrapped type's `can_step` still apply.
+pub struct Clearance<'a, C> {
+    can_enter: &'a C,
+}
+
+impl<'a, C: CanEnter> Clearance<'a, C> {
+    pub fn new(can_enter: &'a C) -> Self {
+        Self { can_enter }
+    }
+
+    fn has_clearance(&self, coord: Coord) -> bool {
+        UNIT_COORDS.iter().any(|unit_coord| {
+            let side = unit_coord.to_coord();
+            let other_side = unit_coord.left90().to_coord();
+            self.can_enter.can_enter(coord + side)
+                && self.can_enter.can_enter(coord + other_side)
+                && self.can_enter.can_enter(coord + side + other_side)
+        })
+    }
+}
+
+impl<'a, C: CanEnter> CanEnter for Clearance<'a, C> {
+    fn can_enter(&self, coord: Coord) -> bool {
+        self.can_enter.can_enter(coord) && self.has_clearance(coord)
+    }
+    fn can_step(&self, step: Step) -> bool {
+        self.can_enter.can_step(step) && self.has_clearance(step.to_coord)
+    }
+}
+
+/// Treats every cell within `border` cells of the edge of a grid of the given size as
+/// impassable, without changing the underlying grid.
+pub struct Bordered<'a, C> {
+    can_enter: &'a C,
+    size: Size,
+    border: u32,
+}
+
+impl<'a, C: CanEnter> Bordered<'a, C> {
+    pub fn new(can_enter: &'a C, size: Size, border: u32) -> Self {
+        Self {
+            can_enter,
+            size,
+            border,
+        }
+    }
+
+    fn is_inside_border(&self, coord: Coord) -> bool {
+        let border = self.border as i64;
+        let (x, y) = (coord.x as i64, coord.y as i64);
+        x >= border && y >= border && x + border < self.size.width() as i64 && y + border < self.size.height() as i64
+    }
+}
+
+impl<'a, C: CanEnter> CanEnter for Bordered<'a, C> {
+    fn can_enter(&self, coord: Coord) -> bool {
+        self.is_inside_border(coord) && self.can_enter.can_enter(coord)
+    }
+    fn can_step(&self, step: Step) -> bool {
+        self.is_inside_border(step.to_coord) && self.can_enter.can_step(step)
+    }
+}
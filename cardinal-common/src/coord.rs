@@ -12,6 +12,22 @@ pub struct CardinalCoord(Coord);
 #[derive(Clone, Copy, Debug)]
 pub struct UnitCoord(CardinalCoord);
 
+/// The order in which distance maps visit neighbours, both while being populated and when
+/// answering neighbour queries such as `direction_to_best_neighbour`. Where several
+/// neighbours are equally good, this order decides which is chosen, so it's defined here
+/// rather than taken from the `direction` crate, whose iteration order could change between
+/// versions.
+pub const CARDINAL_DIRECTIONS: [CardinalDirection; 4] = [
+    CardinalDirection::North,
+    CardinalDirection::East,
+    CardinalDirection::South,
+    CardinalDirection::West,
+];
+
+/// The order in which searches take their first step out of the start: east, north, west,
+/// south. Later steps are considered forward, then left, then right, relative to the step
+/// that reached the cell. Together these decide which of several equally short paths a
+/// search returns.
 pub const UNIT_COORDS: [UnitCoord; 4] = [
     UnitCoord(CardinalCoord(Coord::new(1, 0))),
     UnitCoord(CardinalCoord(Coord::new(0, -1))),
@@ -58,7 +74,7 @@ impl CardinalCoord {
         }
     }
     pub const fn magnitude(self) -> u32 {
-        (self.0.x + self.0.y).abs() as u32
+        (self.0.x + self.0.y).unsigned_abs()
     }
     pub fn to_unit_coord(self) -> UnitCoord {
         match self.0.x.cmp(&0) {
@@ -1,7 +1,7 @@
 use crate::step::Step;
 use direction::CardinalDirection;
 use grid_2d::Coord;
-use std::collections::{vec_deque, VecDeque};
+use std::collections::{vec_deque, HashSet, VecDeque};
 
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
@@ -36,6 +36,17 @@ impl<'a> Iterator for PathIter<'a> {
     }
 }
 
+pub struct PathIntoIter {
+    iter: vec_deque::IntoIter<Step>,
+}
+
+impl Iterator for PathIntoIter {
+    type Item = PathNode;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|step| PathNode::from_step(&step))
+    }
+}
+
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Default, Debug)]
 pub struct Path {
@@ -43,7 +54,7 @@ pub struct Path {
 }
 
 impl Path {
-    pub fn iter(&self) -> PathIter {
+    pub fn iter(&self) -> PathIter<'_> {
         PathIter {
             iter: self.steps.iter(),
         }
@@ -60,7 +71,146 @@ impl Path {
     pub fn clear(&mut self) {
         self.steps.clear();
     }
+    /// True if any step of the path ends at `coord`. The start of the path isn't included.
+    pub fn visits(&self, coord: Coord) -> bool {
+        self.steps.iter().any(|step| step.to_coord == coord)
+    }
+    /// The coord reached by the `n`th step, counting from 0.
+    pub fn nth_coord(&self, n: usize) -> Option<Coord> {
+        self.steps.get(n).map(|step| step.to_coord)
+    }
+    /// True if the path leads from `start` to `goal` in unit steps. An empty path reaches `goal`
+    /// only if it's the same as `start`.
+    pub fn reaches(&self, start: Coord, goal: Coord) -> bool {
+        let mut coord = start;
+        for step in self.steps.iter() {
+            if step.to_coord - step.in_direction.to_coord() != coord {
+                return false;
+            }
+            coord = step.to_coord;
+        }
+        coord == goal
+    }
+    /// Adds a step to the end of the path. It should start where the path currently ends.
+    pub fn push(&mut self, step: Step) {
+        self.steps.push_back(step);
+    }
     pub(crate) fn prepend(&mut self, step: Step) {
         self.steps.push_front(step);
     }
 }
+
+impl IntoIterator for Path {
+    type Item = PathNode;
+    type IntoIter = PathIntoIter;
+    fn into_iter(self) -> Self::IntoIter {
+        PathIntoIter {
+            iter: self.steps.into_iter(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Path {
+    type Item = PathNode;
+    type IntoIter = PathIter<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Returns the (inclusive) top-left and bottom-right corners of the cells visited by the path,
+/// including the start.
+pub fn bounding_box(start: Coord, path: &Path) -> (Coord, Coord) {
+    path.steps
+        .iter()
+        .fold((start, start), |(top_left, bottom_right), step| {
+            (
+                top_left.pairwise_min(step.to_coord),
+                bottom_right.pairwise_max(step.to_coord),
+            )
+        })
+}
+
+/// Returns the coords visited by both paths, including their starts, in the order that `path_a`
+/// visits them.
+pub fn overlap(start_a: Coord, path_a: &Path, start_b: Coord, path_b: &Path) -> Vec<Coord> {
+    let mut coords_b = std::iter::once(start_b)
+        .chain(path_b.iter().map(|node| node.to_coord))
+        .collect::<HashSet<_>>();
+    std::iter::once(start_a)
+        .chain(path_a.iter().map(|node| node.to_coord))
+        .filter(|coord| coords_b.remove(coord))
+        .collect()
+}
+
+/// Returns a `(from, to)` pair for each step of the path, starting from `start`.
+pub fn to_edges(start: Coord, path: &Path) -> Vec<(Coord, Coord)> {
+    let mut from = start;
+    path.iter()
+        .map(|node| {
+            let edge = (from, node.to_coord);
+            from = node.to_coord;
+            edge
+        })
+        .collect()
+}
+
+/// Returns every coord within `radius` of a cell visited by the path, including the start,
+/// measuring distance as the larger of the horizontal and vertical offsets. The set grows with
+/// the square of the radius, so the radius is limited to a `u8`. Coords that would fall outside
+/// the range of `Coord` are left out.
+pub fn inflate(start: Coord, path: &Path, radius: u8) -> HashSet<Coord> {
+    let radius = radius as i32;
+    let mut coords = HashSet::new();
+    for centre in std::iter::once(start).chain(path.iter().map(|node| node.to_coord)) {
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                if let (Some(x), Some(y)) = (centre.x.checked_add(dx), centre.y.checked_add(dy)) {
+                    coords.insert(Coord::new(x, y));
+                }
+            }
+        }
+    }
+    coords
+}
+
+/// Follows a path one step at a time, for agents that move along a path over several turns.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug)]
+pub struct PathCursor {
+    start: Coord,
+    path: Path,
+    index: usize,
+}
+
+impl PathCursor {
+    pub fn new(start: Coord, path: Path) -> Self {
+        Self { start, path, index: 0 }
+    }
+    /// Returns the direction of the next step and advances past it.
+    pub fn next_direction(&mut self) -> Option<CardinalDirection> {
+        let step = self.path.steps.get(self.index)?;
+        self.index += 1;
+        Some(step.in_direction.to_cardinal_direction())
+    }
+    /// The number of steps not yet taken.
+    pub fn remaining(&self) -> usize {
+        self.path.len() - self.index
+    }
+    /// Moves the cursor so that the next step leads out of `current_coord`, for when the agent
+    /// has ended up somewhere other than where the cursor expects. Returns false, leaving the
+    /// cursor unchanged, if `current_coord` isn't on the path.
+    pub fn resync(&mut self, current_coord: Coord) -> bool {
+        if current_coord == self.start {
+            self.index = 0;
+            return true;
+        }
+        match self.path.steps.iter().position(|step| step.to_coord == current_coord) {
+            Some(index) => {
+                self.index = index + 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -35,6 +35,16 @@ impl SeenSet {
         self.grid.size()
     }
 
+    pub fn generation(&self) -> u64 {
+        self.count
+    }
+
+    /// The number of searches that can start before the count wraps. When it runs out, the next
+    /// search resets every cell.
+    pub fn generation_remaining(&self) -> u64 {
+        u64::MAX - self.count
+    }
+
     pub fn build_path_to(&self, end: Coord, path: &mut Path) {
         let mut cell = self.grid.get(end).expect("path end out of bounds");
         debug_assert_eq!(cell.count, self.count, "path end not visited in latest search");
@@ -58,13 +68,33 @@ impl SeenSet {
         }
     }
 
+    /// Fills `waypoints` with the coords of each cell in the parent chain ending at `end`,
+    /// excluding the start and including `end`. After a jump point search these are the jump
+    /// points; every waypoint lies on the path produced by `build_path_to`.
+    pub fn build_waypoints_to(&self, end: Coord, waypoints: &mut Vec<Coord>) {
+        let mut cell = self.grid.get(end).expect("path end out of bounds");
+        debug_assert_eq!(cell.count, self.count, "path end not visited in latest search");
+        let mut coord = end;
+        waypoints.clear();
+        while let Some(in_direction) = cell.in_direction {
+            waypoints.push(coord);
+            coord -= in_direction.to_coord();
+            cell = self.grid.get_checked(coord);
+            debug_assert_eq!(
+                cell.count, self.count,
+                "path includes cell not visited in latest search"
+            );
+        }
+        waypoints.reverse();
+    }
+
     pub fn first_direction_towards(&self, end: Coord) -> Option<CardinalDirection> {
         let mut cell = self.grid.get(end).expect("path end out of bounds");
         debug_assert_eq!(cell.count, self.count, "path end not visited in latest search");
         let mut coord = end;
         let mut ret = None;
         while let Some(in_direction) = cell.in_direction {
-            coord = coord - in_direction.to_coord();
+            coord -= in_direction.to_coord();
             cell = self.grid.get_checked(coord);
             debug_assert_eq!(
                 cell.count, self.count,
@@ -75,10 +105,36 @@ impl SeenSet {
         ret.map(|in_direction| in_direction.to_cardinal_direction())
     }
 
+    pub fn was_seen(&self, coord: Coord) -> bool {
+        matches!(self.grid.get(coord), Some(cell) if cell.count == self.count)
+    }
+
+    pub fn in_direction(&self, coord: Coord) -> Option<CardinalDirection> {
+        self.grid
+            .get(coord)
+            .filter(|cell| cell.count == self.count)
+            .and_then(|cell| cell.in_direction)
+            .map(|in_direction| in_direction.to_cardinal_direction())
+    }
+
+    pub fn cost(&self, coord: Coord) -> Option<u32> {
+        self.grid
+            .get(coord)
+            .filter(|cell| cell.count == self.count)
+            .map(|cell| cell.cost)
+    }
+
     pub fn init(&mut self, start: Coord) {
+        if self.count == u64::MAX {
+            for cell in self.grid.iter_mut() {
+                cell.count = 0;
+            }
+            self.count = 0;
+        }
         self.count += 1;
         let cell = self.grid.get_checked_mut(start);
         cell.count = self.count;
+        cell.cost = 0;
         cell.in_direction = None;
     }
 
@@ -102,3 +158,31 @@ impl SeenSet {
         self.try_visit(jump.to_coord, jump.in_direction, cost)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generation_wraps() {
+        let mut seen_set = SeenSet::new(Size::new(3, 1));
+        seen_set.init(Coord::new(0, 0));
+        seen_set.try_visit_step(
+            Step {
+                to_coord: Coord::new(1, 0),
+                in_direction: crate::coord::UNIT_COORDS[0],
+            },
+            1,
+        );
+        seen_set.count = u64::MAX - 1;
+        seen_set.grid.get_checked_mut(Coord::new(2, 0)).count = u64::MAX;
+        seen_set.init(Coord::new(0, 0));
+        assert_eq!(seen_set.generation_remaining(), 0);
+        assert!(seen_set.was_seen(Coord::new(2, 0)));
+        seen_set.init(Coord::new(0, 0));
+        assert_eq!(seen_set.generation(), 1);
+        assert!(seen_set.was_seen(Coord::new(0, 0)));
+        assert!(!seen_set.was_seen(Coord::new(1, 0)));
+        assert!(!seen_set.was_seen(Coord::new(2, 0)));
+    }
+}
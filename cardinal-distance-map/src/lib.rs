@@ -1,10 +1,9 @@
 pub use direction::CardinalDirection;
-use direction::CardinalDirections;
 use grid_2d::Grid;
 pub use grid_2d::{Coord, Size};
 pub use grid_search_cardinal_common::{can_enter::CanEnter, coord::UnitCoord, step::Step};
 use grid_search_cardinal_common::{
-    coord::UNIT_COORDS,
+    coord::{CARDINAL_DIRECTIONS, UNIT_COORDS},
     path::Path,
     seen_set::{SeenSet, Visit},
 };
@@ -14,20 +13,81 @@ use std::collections::VecDeque;
 
 pub type Distance = u32;
 
+/// Counter used by a `DistanceMap` to tell which cells were written by the most recent
+/// population. Smaller counters reduce the per-cell overhead of a map. When the counter
+/// would overflow, every cell of the map is reset, which costs a pass over the whole grid.
+pub trait Generation: Copy + Eq {
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_add(self, other: Self) -> Option<Self>;
+    /// The number of times the counter can be incremented before it overflows.
+    fn remaining(self) -> u64;
+}
+
+macro_rules! impl_generation {
+    ($($t:ty),*) => {
+        $(impl Generation for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+            fn remaining(self) -> u64 {
+                (<$t>::MAX - self) as u64
+            }
+        })*
+    };
+}
+
+impl_generation!(u32, u64);
+
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-struct Cell {
-    count: u64,
+struct Cell<G> {
+    count: G,
     distance: Distance,
 }
 
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    serde(
+        from = "SerializedDistanceMap<G>",
+        bound(deserialize = "G: Generation + Deserialize<'de>")
+    )
+)]
 #[derive(Debug, Clone)]
-pub struct DistanceMap {
-    count: u64,
-    grid: Grid<Cell>,
+pub struct DistanceMap<G = u64> {
+    count: G,
+    // not serialized, so that the format matches maps saved before this was added
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    reachable_count: usize,
+    grid: Grid<Cell<G>>,
+}
+
+#[cfg(feature = "serialize")]
+#[derive(Deserialize)]
+struct SerializedDistanceMap<G> {
+    count: G,
+    grid: Grid<Cell<G>>,
+}
+
+#[cfg(feature = "serialize")]
+impl<G: Generation> From<SerializedDistanceMap<G>> for DistanceMap<G> {
+    fn from(SerializedDistanceMap { count, grid }: SerializedDistanceMap<G>) -> Self {
+        let reachable_count = grid.iter().filter(|cell| cell.count == count).count();
+        Self {
+            count,
+            reachable_count,
+            grid,
+        }
+    }
 }
 
+/// A `DistanceMap` whose cells are half the size of the default, at the cost of a full reset
+/// every 2^32 populations.
+pub type CompactDistanceMap = DistanceMap<u32>;
+
 #[derive(Debug, Clone)]
 struct PopulateNode {
     coord: Coord,
@@ -37,6 +97,8 @@ struct PopulateNode {
 #[derive(Default, Debug, Clone)]
 pub struct PopulateContext {
     queue: VecDeque<PopulateNode>,
+    seeds: Vec<PopulateNode>,
+    previous_distances: Vec<Option<Distance>>,
 }
 
 #[derive(Debug, Clone)]
@@ -51,13 +113,24 @@ pub struct SearchContext {
     queue: VecDeque<SearchNode>,
 }
 
+/// Distance map towards a single goal, which is only repopulated when the goal moves. Any
+/// number of pursuers can then read their next step towards the goal.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ChaseField {
+    distance_map: DistanceMap,
+    populate_context: PopulateContext,
+    max_distance: Distance,
+    goal: Option<Coord>,
+}
+
 struct SearchState {
     distance_to_goal: Distance,
     closest_coord: Coord,
 }
 
-struct SearchInstance<'a, C: 'a + CanEnter> {
-    distance_map: &'a DistanceMap,
+struct SearchInstance<'a, C: 'a + CanEnter, G: Generation> {
+    distance_map: &'a DistanceMap<G>,
     can_enter: &'a C,
     max_distance: Distance,
     search_state: SearchState,
@@ -68,7 +141,7 @@ struct Prune {
     distance_to_goal: Distance,
 }
 
-impl<'a, C: CanEnter> SearchInstance<'a, C> {
+impl<'a, C: CanEnter, G: Generation> SearchInstance<'a, C, G> {
     fn prune(&self, prune: Prune) -> bool {
         let remaining_distance = self.max_distance - prune.current_distance;
         if let Some(best_possible_distance_through_cell) = prune.distance_to_goal.checked_sub(remaining_distance) {
@@ -132,27 +205,85 @@ impl<'a> Deserialize<'a> for SearchContext {
 
 impl DistanceMap {
     pub fn new(size: Size) -> Self {
+        Self::with_generation(size)
+    }
+}
+
+impl<G: Generation> DistanceMap<G> {
+    pub fn with_generation(size: Size) -> Self {
         Self {
-            count: 1,
-            grid: Grid::new_fn(size, |_| Cell { count: 0, distance: 0 }),
+            count: G::ONE,
+            reachable_count: 0,
+            grid: Grid::new_fn(size, |_| Cell {
+                count: G::ZERO,
+                distance: 0,
+            }),
         }
     }
 
+    /// Changes each time the map is cleared or populated. Callers can tag their own per-cell
+    /// data with this value to tell whether it's as recent as the map. When the counter would
+    /// overflow, every cell is reset and the generation starts again from a small value, so
+    /// tags from before the reset may match later generations. Callers keeping tags should
+    /// discard them when `generation_remaining` is too small for the next clear or population
+    /// (a flee population uses two generations). This returns `G` rather than `u64` so that
+    /// tags can be stored at the same width as the map's own counters, e.g. `u32` for a
+    /// `CompactDistanceMap`.
+    pub fn generation(&self) -> G {
+        self.count
+    }
+
+    /// The number of generations left before the counter resets.
+    pub fn generation_remaining(&self) -> u64 {
+        self.count.remaining()
+    }
+
     pub fn clear(&mut self) {
-        self.count += 1;
+        self.reserve_generations(1);
+        self.count = self.count.checked_add(G::ONE).unwrap();
+        self.reachable_count = 0;
+    }
+
+    /// The number of cells written by the most recent population.
+    pub fn reachable_count(&self) -> usize {
+        self.reachable_count
+    }
+
+    /// True if the most recent population reached at least one cell, and the map hasn't been
+    /// cleared since.
+    pub fn is_populated(&self) -> bool {
+        self.reachable_count > 0
+    }
+
+    /// Makes sure the count can be incremented `n` more times without overflowing, resetting
+    /// every cell if it can't.
+    fn reserve_generations(&mut self, n: usize) {
+        let mut count = Some(self.count);
+        for _ in 0..n {
+            count = count.and_then(|count| count.checked_add(G::ONE));
+        }
+        if count.is_none() {
+            for cell in self.grid.iter_mut() {
+                cell.count = G::ZERO;
+            }
+            self.count = G::ONE;
+        }
     }
 
     pub fn size(&self) -> Size {
         self.grid.size()
     }
 
+    /// Returns the direction to the neighbour closest to the map's seeds, provided it's no
+    /// further than `coord`. Ties go to the last direction in `CARDINAL_DIRECTIONS`.
+    #[must_use]
     pub fn direction_to_best_neighbour(&self, coord: Coord) -> Option<CardinalDirection> {
-        let mut shortest_distance = std::u32::MAX;
+        let mut shortest_distance = u32::MAX;
         let mut direction_to_best_neighbour = None;
         if let Some(distance) = self.distance(coord) {
             shortest_distance = distance;
         }
-        for direction in CardinalDirections {
+        for &direction in &CARDINAL_DIRECTIONS {
             let neighbour_coord = coord + direction.coord();
             if let Some(distance) = self.distance(neighbour_coord) {
                 if distance <= shortest_distance {
@@ -164,6 +295,113 @@ impl DistanceMap {
         direction_to_best_neighbour
     }
 
+    /// Returns the direction to the neighbour furthest from the map's seeds, provided it's
+    /// further than `coord`. Neighbours that weren't reached are ignored, so near the edge of a
+    /// map populated with a max distance this stops pointing outwards. As with
+    /// `direction_to_best_neighbour`, ties go to the last direction in `CARDINAL_DIRECTIONS`.
+    #[must_use]
+    pub fn direction_away(&self, coord: Coord) -> Option<CardinalDirection> {
+        let distance_here = self.distance(coord)?;
+        let mut longest_distance = distance_here;
+        let mut direction_away = None;
+        for &direction in &CARDINAL_DIRECTIONS {
+            let neighbour_coord = coord + direction.coord();
+            if let Some(distance) = self.distance(neighbour_coord) {
+                if distance > distance_here && distance >= longest_distance {
+                    longest_distance = distance;
+                    direction_away = Some(direction);
+                }
+            }
+        }
+        direction_away
+    }
+
+    /// Replaces the contents of `paths` with the shortest paths from `start` to the map's
+    /// seeds, stopping after `max_paths` because their number can grow exponentially with
+    /// distance. Each neighbour one step closer to the seeds than the current cell lies on some
+    /// shortest path, so the paths are found by a depth-first search over the map, trying
+    /// neighbours in the order of `CARDINAL_DIRECTIONS`. As with `direction_to_best_neighbour`,
+    /// this assumes that every step taken while populating the map can be taken in reverse.
+    pub fn all_optimal_paths(&self, start: Coord, max_paths: usize, paths: &mut Vec<Path>) {
+        struct Frame {
+            coord: Coord,
+            distance: Distance,
+            direction_index: usize,
+            is_end: bool,
+        }
+        paths.clear();
+        let distance = match self.distance(start) {
+            Some(distance) => distance,
+            None => return,
+        };
+        let mut stack = vec![Frame {
+            coord: start,
+            distance,
+            direction_index: 0,
+            is_end: true,
+        }];
+        let mut steps = Vec::new();
+        while paths.len() < max_paths {
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => break,
+            };
+            if let Some(&direction) = CARDINAL_DIRECTIONS.get(frame.direction_index) {
+                frame.direction_index += 1;
+                let neighbour_coord = frame.coord + direction.coord();
+                if let Some(neighbour_distance) = self.distance(neighbour_coord) {
+                    if neighbour_distance + 1 == frame.distance {
+                        frame.is_end = false;
+                        steps.push(Step {
+                            to_coord: neighbour_coord,
+                            in_direction: UnitCoord::from_cardinal_direction(direction),
+                        });
+                        stack.push(Frame {
+                            coord: neighbour_coord,
+                            distance: neighbour_distance,
+                            direction_index: 0,
+                            is_end: true,
+                        });
+                    }
+                }
+            } else {
+                if frame.is_end {
+                    let mut path = Path::default();
+                    for &step in &steps {
+                        path.push(step);
+                    }
+                    paths.push(path);
+                }
+                stack.pop();
+                steps.pop();
+            }
+        }
+    }
+
+    /// Iterates over the cells written by the most recent population, along with their
+    /// distances, in row-major order.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Coord, Distance)> {
+        self.grid
+            .enumerate()
+            .filter(move |(_, cell)| cell.count == self.count)
+            .map(|(coord, cell)| (coord, cell.distance))
+    }
+
+    /// Groups the populated cells by distance, in ascending order of distance. Cells within
+    /// a layer are in row-major order.
+    pub fn layers(&self) -> impl Iterator<Item = (Distance, Vec<Coord>)> {
+        let mut cells = self.iter().collect::<Vec<_>>();
+        cells.sort_by_key(|&(_, distance)| distance);
+        let mut layers: Vec<(Distance, Vec<Coord>)> = Vec::new();
+        for (coord, distance) in cells {
+            match layers.last_mut() {
+                Some((layer_distance, coords)) if *layer_distance == distance => coords.push(coord),
+                _ => layers.push((distance, vec![coord])),
+            }
+        }
+        layers.into_iter()
+    }
+
     pub fn distance(&self, coord: Coord) -> Option<Distance> {
         if let Some(cell) = self.grid.get(coord) {
             if cell.count == self.count {
@@ -183,27 +421,67 @@ impl PopulateContext {
         self.queue.push_front(PopulateNode { coord, distance: 0 });
     }
 
-    pub fn populate_approach<C: CanEnter>(
+    /// Adds a seed which starts at `distance` rather than 0, so that a cell ends up with the
+    /// smallest of each seed's distance plus the number of steps from that seed. Seeds further
+    /// than the maximum distance are ignored. `populate_flee` treats every seed as distance 0.
+    pub fn add_with_distance(&mut self, coord: Coord, distance: Distance) {
+        self.queue.push_front(PopulateNode { coord, distance });
+    }
+
+    /// Populates the map, calling `visit` with each cell and its distance as it's written.
+    /// Population stops early if `visit` returns true.
+    fn populate_approach_core<C, G, F>(
         &mut self,
         can_enter: &C,
         max_distance: Distance,
-        distance_map: &mut DistanceMap,
-    ) {
+        distance_map: &mut DistanceMap<G>,
+        mut visit: F,
+    ) where
+        C: CanEnter,
+        G: Generation,
+        F: FnMut(Coord, Distance) -> bool,
+    {
         distance_map.clear();
-        for node in self.queue.iter() {
-            if let Some(cell) = distance_map.grid.get_mut(node.coord) {
-                cell.count = distance_map.count;
-                cell.distance = 0;
+        // Seeds wait here, closest last, until the queue reaches their distance. Taking them in
+        // that order keeps the queue sorted, so each cell is still written once.
+        self.seeds.clear();
+        self.seeds.extend(self.queue.drain(..).rev());
+        self.seeds.sort_by_key(|seed| std::cmp::Reverse(seed.distance));
+        loop {
+            let seed_is_next = match (self.seeds.last(), self.queue.back()) {
+                (Some(seed), Some(node)) => seed.distance <= node.distance,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if seed_is_next {
+                let seed = self.seeds.pop().unwrap();
+                if seed.distance > max_distance {
+                    continue;
+                }
+                if let Some(cell) = distance_map.grid.get_mut(seed.coord) {
+                    if cell.count != distance_map.count {
+                        distance_map.reachable_count += 1;
+                        cell.count = distance_map.count;
+                        cell.distance = seed.distance;
+                        if visit(seed.coord, seed.distance) {
+                            self.queue.clear();
+                            self.seeds.clear();
+                            return;
+                        }
+                        if seed.distance != max_distance {
+                            self.queue.push_back(seed);
+                        }
+                    }
+                }
+                continue;
             }
-        }
-        if max_distance == 0 {
-            self.queue.clear();
-            return;
-        }
-        while let Some(PopulateNode { coord, distance }) = self.queue.pop_back() {
+            let PopulateNode { coord, distance } = match self.queue.pop_back() {
+                Some(node) => node,
+                None => break,
+            };
             debug_assert!(distance < max_distance);
             let neighbour_distance = distance + 1;
-            for direction in CardinalDirections {
+            for &direction in &CARDINAL_DIRECTIONS {
                 let neighbour_coord = coord + direction.coord();
                 if can_enter.can_step(Step {
                     to_coord: neighbour_coord,
@@ -211,8 +489,14 @@ impl PopulateContext {
                 }) {
                     if let Some(cell) = distance_map.grid.get_mut(neighbour_coord) {
                         if cell.count != distance_map.count {
+                            distance_map.reachable_count += 1;
                             cell.count = distance_map.count;
                             cell.distance = neighbour_distance;
+                            if visit(neighbour_coord, neighbour_distance) {
+                                self.queue.clear();
+                                self.seeds.clear();
+                                return;
+                            }
                             if neighbour_distance != max_distance {
                                 self.queue.push_front(PopulateNode {
                                     coord: neighbour_coord,
@@ -226,15 +510,103 @@ impl PopulateContext {
         }
     }
 
-    pub fn populate_flee<C: CanEnter>(
+    pub fn populate_approach<C: CanEnter, G: Generation>(
+        &mut self,
+        can_enter: &C,
+        max_distance: Distance,
+        distance_map: &mut DistanceMap<G>,
+    ) {
+        self.populate_approach_core(can_enter, max_distance, distance_map, |_, _| false);
+    }
+
+    /// Like `populate_approach`, but stops as soon as `stop` returns true for a newly written
+    /// cell. Cells further than that cell from the origins are left unpopulated.
+    pub fn populate_approach_until<C, G, F>(
+        &mut self,
+        can_enter: &C,
+        max_distance: Distance,
+        distance_map: &mut DistanceMap<G>,
+        stop: F,
+    ) where
+        C: CanEnter,
+        G: Generation,
+        F: FnMut(Coord, Distance) -> bool,
+    {
+        self.populate_approach_core(can_enter, max_distance, distance_map, stop);
+    }
+
+    /// Like `populate_approach`, but also collects into `frontier` each cell at `max_distance`
+    /// with an enterable neighbour that was left unpopulated because of the bound.
+    pub fn populate_approach_frontier<C: CanEnter, G: Generation>(
+        &mut self,
+        can_enter: &C,
+        max_distance: Distance,
+        distance_map: &mut DistanceMap<G>,
+        frontier: &mut Vec<Coord>,
+    ) {
+        frontier.clear();
+        self.populate_approach_core(can_enter, max_distance, distance_map, |coord, distance| {
+            if distance == max_distance {
+                frontier.push(coord);
+            }
+            false
+        });
+        frontier.retain(|&coord| {
+            CARDINAL_DIRECTIONS.iter().any(|&direction| {
+                let neighbour_coord = coord + direction.coord();
+                distance_map.grid.get(neighbour_coord).is_some()
+                    && distance_map.distance(neighbour_coord).is_none()
+                    && can_enter.can_step(Step {
+                        to_coord: neighbour_coord,
+                        in_direction: UnitCoord::from_cardinal_direction(direction),
+                    })
+            })
+        });
+    }
+
+    /// Like `populate_approach`, but also collects into `diff` every coord whose distance
+    /// (or whether it has a distance at all) changed from the map's previous population.
+    pub fn populate_approach_diff<C: CanEnter, G: Generation>(
+        &mut self,
+        can_enter: &C,
+        max_distance: Distance,
+        distance_map: &mut DistanceMap<G>,
+        diff: &mut Vec<Coord>,
+    ) {
+        diff.clear();
+        let mut previous_distances = std::mem::take(&mut self.previous_distances);
+        previous_distances.clear();
+        previous_distances.extend(
+            distance_map
+                .grid
+                .iter()
+                .map(|cell| (cell.count == distance_map.count).then_some(cell.distance)),
+        );
+        self.populate_approach(can_enter, max_distance, distance_map);
+        for ((coord, cell), &previous_distance) in distance_map.grid.enumerate().zip(previous_distances.iter()) {
+            let distance = (cell.count == distance_map.count).then_some(cell.distance);
+            if distance != previous_distance {
+                diff.push(coord);
+            }
+        }
+        self.previous_distances = previous_distances;
+    }
+
+    pub fn populate_flee<C: CanEnter, G: Generation>(
         &mut self,
         can_enter: &C,
         max_distance: Distance,
-        distance_map: &mut DistanceMap,
+        distance_map: &mut DistanceMap<G>,
     ) {
-        distance_map.count += 1;
-        for node in self.queue.iter() {
+        // the second pass below relies on telling apart cells from the first pass
+        distance_map.reserve_generations(2);
+        distance_map.clear();
+        for node in self.queue.iter_mut() {
+            node.distance = 0;
             if let Some(cell) = distance_map.grid.get_mut(node.coord) {
+                if cell.count != distance_map.count {
+                    distance_map.reachable_count += 1;
+                }
                 cell.count = distance_map.count;
                 cell.distance = 0;
             }
@@ -250,7 +622,7 @@ impl PopulateContext {
                 break;
             }
             let neighbour_distance = distance + 1;
-            for direction in CardinalDirections {
+            for &direction in &CARDINAL_DIRECTIONS {
                 let neighbour_coord = coord + direction.coord();
                 if can_enter.can_step(Step {
                     to_coord: neighbour_coord,
@@ -258,6 +630,7 @@ impl PopulateContext {
                 }) {
                     if let Some(cell) = distance_map.grid.get_mut(neighbour_coord) {
                         if cell.count != distance_map.count {
+                            distance_map.reachable_count += 1;
                             cell.count = distance_map.count;
                             cell.distance = neighbour_distance;
                             self.queue.push_front(PopulateNode {
@@ -273,22 +646,27 @@ impl PopulateContext {
             return;
         }
         // at this point we know that all the nodes in the queue have a distance of max_distance
-        distance_map.count += 1;
+        let first_pass_count = distance_map.count;
+        distance_map.clear();
         for node in self.queue.iter_mut() {
             debug_assert!(node.distance <= max_distance);
             node.distance = 0;
             if let Some(cell) = distance_map.grid.get_mut(node.coord) {
+                if cell.count != distance_map.count {
+                    distance_map.reachable_count += 1;
+                }
                 cell.count = distance_map.count;
                 cell.distance = 0;
             }
         }
         while let Some(PopulateNode { coord, distance }) = self.queue.pop_back() {
             let neighbour_distance = distance + 1;
-            for direction in CardinalDirections {
+            for &direction in &CARDINAL_DIRECTIONS {
                 let neighbour_coord = coord + direction.coord();
                 if let Some(cell) = distance_map.grid.get_mut(neighbour_coord) {
-                    if cell.count == distance_map.count - 1 {
-                        cell.count += 1;
+                    if cell.count == first_pass_count {
+                        distance_map.reachable_count += 1;
+                        cell.count = distance_map.count;
                         cell.distance = neighbour_distance;
                         self.queue.push_front(PopulateNode {
                             coord: neighbour_coord,
@@ -309,13 +687,29 @@ impl SearchContext {
         }
     }
 
-    fn search_core<C: CanEnter>(
+    /// Incremented at the start of each search whose start was reached by the distance map.
+    /// Callers can tag their own per-cell data with this value to tell whether it was written
+    /// during the most recent search.
+    pub fn generation(&self) -> u64 {
+        self.seen_set.generation()
+    }
+
+    /// The number of searches that can run before the generation wraps, at which point every
+    /// cell is reset.
+    pub fn generation_remaining(&self) -> u64 {
+        self.seen_set.generation_remaining()
+    }
+
+    fn search_core<C: CanEnter, G: Generation>(
         &mut self,
         can_enter: &C,
         start: Coord,
         max_distance: Distance,
-        distance_map: &DistanceMap,
+        distance_map: &DistanceMap<G>,
     ) -> Option<Coord> {
+        if distance_map.size() != self.seen_set.size() {
+            return None;
+        }
         let search_state = if let Some(distance_to_goal) = distance_map.distance(start) {
             SearchState {
                 distance_to_goal,
@@ -354,12 +748,15 @@ impl SearchContext {
         Some(instance.search_state.closest_coord)
     }
 
-    pub fn search_path<C: CanEnter>(
+    /// Finds a path from `start` towards the closest cell to the distance map's origins. The
+    /// path is left unchanged if no search took place, which includes when the context and
+    /// the distance map differ in size.
+    pub fn search_path<C: CanEnter, G: Generation>(
         &mut self,
         can_enter: &C,
         start: Coord,
         max_distance: Distance,
-        distance_map: &DistanceMap,
+        distance_map: &DistanceMap<G>,
         path: &mut Path,
     ) {
         if let Some(end) = self.search_core(can_enter, start, max_distance, distance_map) {
@@ -367,12 +764,13 @@ impl SearchContext {
         }
     }
 
-    pub fn search_first<C: CanEnter>(
+    #[must_use]
+    pub fn search_first<C: CanEnter, G: Generation>(
         &mut self,
         can_enter: &C,
         start: Coord,
         max_distance: Distance,
-        distance_map: &DistanceMap,
+        distance_map: &DistanceMap<G>,
     ) -> Option<CardinalDirection> {
         if let Some(end) = self.search_core(can_enter, start, max_distance, distance_map) {
             self.seen_set.first_direction_towards(end)
@@ -382,6 +780,100 @@ impl SearchContext {
     }
 }
 
+impl ChaseField {
+    pub fn new(size: Size, max_distance: Distance) -> Self {
+        Self {
+            distance_map: DistanceMap::new(size),
+            populate_context: PopulateContext::default(),
+            max_distance,
+            goal: None,
+        }
+    }
+
+    pub fn goal(&self) -> Option<Coord> {
+        self.goal
+    }
+
+    pub fn distance_map(&self) -> &DistanceMap {
+        &self.distance_map
+    }
+
+    /// Repopulates the field if `goal` differs from the goal of the previous update. Call
+    /// `invalidate` first if the world has changed since then.
+    pub fn update<C: CanEnter>(&mut self, can_enter: &C, goal: Coord) {
+        if self.goal == Some(goal) {
+            return;
+        }
+        self.goal = Some(goal);
+        self.populate_context.add(goal);
+        self.populate_context
+            .populate_approach(can_enter, self.max_distance, &mut self.distance_map);
+    }
+
+    pub fn invalidate(&mut self) {
+        self.goal = None;
+        self.distance_map.clear();
+    }
+
+    #[must_use]
+    pub fn next_step(&self, from: Coord) -> Option<CardinalDirection> {
+        self.distance_map.direction_to_best_neighbour(from)
+    }
+}
+
+/// Treats the cells reached by the most recent population of a distance map as enterable, and
+/// every other cell as impassable, so that a later search is confined to those cells.
+pub struct ReachableMask<'a, G = u64> {
+    distance_map: &'a DistanceMap<G>,
+}
+
+impl<'a, G: Generation> ReachableMask<'a, G> {
+    pub fn new(distance_map: &'a DistanceMap<G>) -> Self {
+        Self { distance_map }
+    }
+}
+
+impl<'a, G: Generation> CanEnter for ReachableMask<'a, G> {
+    fn can_enter(&self, coord: Coord) -> bool {
+        self.distance_map.distance(coord).is_some()
+    }
+}
+
+/// Treats cells closer than `min_distance` to a hazard as impassable. `hazards` should be
+/// populated by `populate_approach` seeded at the hazards, with a max distance of at least
+/// `min_distance - 1`. Cells it didn't reach are treated as clear.
+pub struct HazardClearance<'a, C, G = u64> {
+    can_enter: &'a C,
+    hazards: &'a DistanceMap<G>,
+    min_distance: Distance,
+}
+
+impl<'a, C: CanEnter, G: Generation> HazardClearance<'a, C, G> {
+    pub fn new(can_enter: &'a C, hazards: &'a DistanceMap<G>, min_distance: Distance) -> Self {
+        Self {
+            can_enter,
+            hazards,
+            min_distance,
+        }
+    }
+
+    fn is_clear(&self, coord: Coord) -> bool {
+        match self.hazards.distance(coord) {
+            Some(distance) => distance >= self.min_distance,
+            None => true,
+        }
+    }
+}
+
+impl<'a, C: CanEnter, G: Generation> CanEnter for HazardClearance<'a, C, G> {
+    fn can_enter(&self, coord: Coord) -> bool {
+        self.can_enter.can_enter(coord) && self.is_clear(coord)
+    }
+    fn can_step(&self, step: Step) -> bool {
+        self.can_enter.can_step(step) && self.is_clear(step.to_coord)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -527,6 +1019,387 @@ mod test {
         assert_eq!(&directions, &[]);
     }
 
+    #[test]
+    fn reachable_within() {
+        let Test { world, .. } = Test::from_str_slice(GRID_B);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add(Coord::new(4, 4));
+        populate_context.populate_approach(&world, 2, &mut distance_map);
+        let reachable = distance_map.iter().collect::<Vec<_>>();
+        assert_eq!(reachable.len(), 13);
+        assert!(reachable.contains(&(Coord::new(4, 4), 0)));
+        assert!(reachable.contains(&(Coord::new(5, 5), 2)));
+        assert!(reachable
+            .iter()
+            .all(|&(coord, distance)| coord.manhattan_distance(Coord::new(4, 4)) == distance));
+    }
+
+    #[test]
+    fn layers() {
+        let Test { world, goals } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        for &coord in &goals {
+            populate_context.add(coord);
+        }
+        populate_context.populate_approach(&world, 7, &mut distance_map);
+        let layers = distance_map.layers().collect::<Vec<_>>();
+        assert_eq!(layers.len(), 8);
+        assert_eq!(layers[0], (0, vec![Coord::new(1, 8)]));
+        assert_eq!(
+            layers[1],
+            (
+                1,
+                vec![Coord::new(1, 7), Coord::new(0, 8), Coord::new(2, 8), Coord::new(1, 9)]
+            )
+        );
+        assert_eq!(layers[7].0, 7);
+        assert_eq!(
+            layers.iter().map(|(_, coords)| coords.len()).sum::<usize>(),
+            distance_map.iter().count()
+        );
+    }
+
+    #[test]
+    fn generation() {
+        let Test { world, goals } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        let generation = distance_map.generation();
+        for &coord in &goals {
+            populate_context.add(coord);
+        }
+        populate_context.populate_approach(&world, 7, &mut distance_map);
+        assert_ne!(distance_map.generation(), generation);
+        let generation = distance_map.generation();
+        distance_map.clear();
+        assert_ne!(distance_map.generation(), generation);
+        let mut distance_map = CompactDistanceMap::with_generation(world.grid.size());
+        distance_map.count = u32::MAX - 1;
+        assert_eq!(distance_map.generation_remaining(), 1);
+        distance_map.clear();
+        assert_eq!(distance_map.generation_remaining(), 0);
+        distance_map.clear();
+        assert!(distance_map.generation_remaining() > 1);
+    }
+
+    #[test]
+    fn search_context_generation() {
+        let Test { world, goals } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        let mut search_context = SearchContext::new(distance_map.size());
+        for &coord in &goals {
+            populate_context.add(coord);
+        }
+        populate_context.populate_approach(&world, 20, &mut distance_map);
+        let generation = search_context.generation();
+        let mut path = Path::default();
+        search_context.search_path(&world, Coord::new(7, 7), 100, &distance_map, &mut path);
+        assert_eq!(search_context.generation(), generation + 1);
+        let _ = search_context.search_first(&world, Coord::new(7, 7), 100, &distance_map);
+        assert_eq!(search_context.generation(), generation + 2);
+    }
+
+    #[test]
+    fn reachable_count() {
+        let Test { world, goals } = Test::from_str_slice(GRID_C);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        assert_eq!(distance_map.reachable_count(), 0);
+        for &coord in &goals {
+            populate_context.add(coord);
+            populate_context.add(coord);
+        }
+        populate_context.populate_approach(&world, 4, &mut distance_map);
+        assert_eq!(distance_map.reachable_count(), distance_map.iter().count());
+        for &coord in &goals {
+            populate_context.add(coord);
+        }
+        populate_context.populate_flee(&world, 10, &mut distance_map);
+        assert_eq!(distance_map.reachable_count(), distance_map.iter().count());
+        distance_map.clear();
+        assert_eq!(distance_map.reachable_count(), 0);
+    }
+
+    #[test]
+    fn clear() {
+        let Test { world, goals } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        assert!(!distance_map.is_populated());
+        for &coord in &goals {
+            populate_context.add(coord);
+        }
+        populate_context.populate_approach(&world, 20, &mut distance_map);
+        assert!(distance_map.is_populated());
+        distance_map.clear();
+        assert!(!distance_map.is_populated());
+        for coord in distance_map.size().coord_iter_row_major() {
+            assert_eq!(distance_map.distance(coord), None);
+        }
+        assert_eq!(distance_map.distance(Coord::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn direction_away() {
+        let Test { world, .. } = Test::from_str_slice(GRID_B);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add(Coord::new(4, 4));
+        populate_context.populate_approach(&world, 4, &mut distance_map);
+        let direction = |x, y| distance_map.direction_away(Coord::new(x, y));
+        assert_eq!(direction(4, 6), Some(CardinalDirection::West));
+        assert_eq!(direction(2, 4), Some(CardinalDirection::West));
+        assert_eq!(direction(5, 5), Some(CardinalDirection::South));
+        assert_eq!(direction(4, 5), Some(CardinalDirection::West));
+        // the same ties, broken the same way, when approaching
+        let best = |x, y| distance_map.direction_to_best_neighbour(Coord::new(x, y));
+        assert_eq!(best(5, 5), Some(CardinalDirection::West));
+        assert_eq!(best(3, 3), Some(CardinalDirection::South));
+        assert_eq!(direction(4, 0), None);
+        assert_eq!(direction(0, 0), None);
+    }
+
+    #[test]
+    fn tie_break_order() {
+        let Test { world, .. } = Test::from_str_slice(GRID_B);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add(Coord::new(4, 4));
+        populate_context.populate_approach(&world, 20, &mut distance_map);
+        let direction = |x, y| distance_map.direction_to_best_neighbour(Coord::new(x, y));
+        assert_eq!(direction(5, 5), Some(CardinalDirection::West));
+        assert_eq!(direction(3, 5), Some(CardinalDirection::East));
+        assert_eq!(direction(5, 3), Some(CardinalDirection::West));
+        assert_eq!(direction(3, 3), Some(CardinalDirection::South));
+    }
+
+    #[test]
+    fn seeded_distances() {
+        let Test { world, .. } = Test::from_str_slice(GRID_B);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add_with_distance(Coord::new(8, 5), 5);
+        populate_context.add(Coord::new(2, 5));
+        populate_context.populate_approach(&world, 20, &mut distance_map);
+        let distance = |x, y| distance_map.distance(Coord::new(x, y));
+        assert_eq!(distance(5, 5), Some(3));
+        assert_eq!(distance(8, 5), Some(5));
+        assert_eq!(distance(9, 5), Some(6));
+        assert_eq!(distance(9, 0), Some(11));
+        assert_eq!(distance_map.reachable_count(), distance_map.iter().count());
+        populate_context.add(Coord::new(2, 5));
+        populate_context.add_with_distance(Coord::new(3, 5), 5);
+        populate_context.add_with_distance(Coord::new(9, 9), 30);
+        populate_context.populate_approach(&world, 20, &mut distance_map);
+        assert_eq!(distance_map.distance(Coord::new(3, 5)), Some(1));
+        assert_eq!(distance_map.distance(Coord::new(9, 9)), Some(11));
+    }
+
+    #[test]
+    fn reachable_mask() {
+        let Test { world, goals } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut region = DistanceMap::new(world.grid.size());
+        for &coord in &goals {
+            populate_context.add(coord);
+        }
+        populate_context.populate_approach(&world, 3, &mut region);
+        let mask = ReachableMask::new(&region);
+        assert!(mask.can_enter(goals[0]));
+        assert!(!mask.can_enter(Coord::new(0, 0)));
+        assert!(!mask.can_enter(Coord::new(-1, 0)));
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add(Coord::new(0, 8));
+        populate_context.populate_approach(&mask, 100, &mut distance_map);
+        assert_eq!(distance_map.reachable_count(), region.reachable_count());
+        for (coord, _) in distance_map.iter() {
+            assert!(region.distance(coord).is_some());
+        }
+    }
+
+    #[test]
+    fn hazard_clearance() {
+        let Test { world, .. } = Test::from_str_slice(GRID_B);
+        let mut populate_context = PopulateContext::default();
+        let mut hazards = DistanceMap::new(world.grid.size());
+        populate_context.add(Coord::new(5, 5));
+        populate_context.populate_approach(&world, 2, &mut hazards);
+        let (start, goal) = (Coord::new(9, 5), Coord::new(0, 5));
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add(goal);
+        populate_context.populate_approach(&world, 100, &mut distance_map);
+        assert_eq!(distance_map.distance(start), Some(9));
+        let avoid = HazardClearance::new(&world, &hazards, 3);
+        assert!(!avoid.can_enter(Coord::new(5, 7)));
+        assert!(avoid.can_enter(Coord::new(5, 8)));
+        populate_context.add(goal);
+        populate_context.populate_approach(&avoid, 100, &mut distance_map);
+        assert_eq!(distance_map.distance(start), Some(15));
+        assert_eq!(distance_map.distance(Coord::new(4, 5)), None);
+    }
+
+    #[test]
+    fn hazard_clearance_keeps_can_step() {
+        struct NoEastSteps<'a> {
+            world: &'a World,
+        }
+        impl<'a> CanEnter for NoEastSteps<'a> {
+            fn can_enter(&self, coord: Coord) -> bool {
+                self.world.can_enter(coord)
+            }
+            fn can_step(&self, step: Step) -> bool {
+                step.in_direction.to_cardinal_direction() != CardinalDirection::East && self.can_enter(step.to_coord)
+            }
+        }
+        let Test { world, .. } = Test::from_str_slice(GRID_B);
+        let mut populate_context = PopulateContext::default();
+        let mut hazards = DistanceMap::new(world.grid.size());
+        populate_context.add(Coord::new(5, 5));
+        populate_context.populate_approach(&world, 2, &mut hazards);
+        let no_east_steps = NoEastSteps { world: &world };
+        let avoid = HazardClearance::new(&no_east_steps, &hazards, 3);
+        let step = |to_coord, direction| Step {
+            to_coord,
+            in_direction: UnitCoord::from_cardinal_direction(direction),
+        };
+        assert!(avoid.can_step(step(Coord::new(1, 1), CardinalDirection::West)));
+        assert!(!avoid.can_step(step(Coord::new(1, 1), CardinalDirection::East)));
+        assert!(!avoid.can_step(step(Coord::new(4, 5), CardinalDirection::West)));
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add(Coord::new(0, 5));
+        populate_context.populate_approach(&avoid, 100, &mut distance_map);
+        assert_eq!(distance_map.distance(Coord::new(0, 6)), Some(1));
+        assert_eq!(distance_map.distance(Coord::new(9, 5)), None);
+    }
+
+    const GRID_TWO_PATHS: &[&str] = &["#####", "#@..#", "#.#.#", "#...#", "#####"];
+
+    #[test]
+    fn all_optimal_paths() {
+        let Test { world, goals } = Test::from_str_slice(GRID_TWO_PATHS);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add(goals[0]);
+        populate_context.populate_approach(&world, 100, &mut distance_map);
+        let start = Coord::new(3, 3);
+        let mut paths = Vec::new();
+        distance_map.all_optimal_paths(start, 10, &mut paths);
+        assert_eq!(paths.len(), 2);
+        let directions = paths
+            .iter()
+            .map(|path| path.iter().map(|node| node.in_direction).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        use CardinalDirection::*;
+        assert_eq!(
+            directions,
+            vec![vec![North, North, West, West], vec![West, West, North, North]]
+        );
+        for path in &paths {
+            assert!(path.reaches(start, goals[0]));
+        }
+        distance_map.all_optimal_paths(start, 1, &mut paths);
+        assert_eq!(paths.len(), 1);
+        distance_map.all_optimal_paths(goals[0], 10, &mut paths);
+        assert_eq!(paths.len(), 1);
+        assert!(paths[0].is_empty());
+        distance_map.all_optimal_paths(Coord::new(0, 0), 10, &mut paths);
+        assert!(paths.is_empty());
+        let Test { world, .. } = Test::from_str_slice(&["...", "...", "..."]);
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        populate_context.add(Coord::new(0, 0));
+        populate_context.populate_approach(&world, 100, &mut distance_map);
+        distance_map.all_optimal_paths(Coord::new(2, 2), 100, &mut paths);
+        assert_eq!(paths.len(), 6);
+    }
+
+    #[test]
+    fn approach_until() {
+        let Test { world, goals } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        for &coord in &goals {
+            populate_context.add(coord);
+        }
+        let mut settled = Vec::new();
+        populate_context.populate_approach_until(&world, 100, &mut distance_map, |coord, distance| {
+            settled.push(distance);
+            coord == Coord::new(4, 6)
+        });
+        assert_eq!(distance_map.distance(Coord::new(4, 6)), Some(5));
+        assert_eq!(distance_map.distance(Coord::new(1, 9)), Some(1));
+        assert_eq!(distance_map.distance(Coord::new(4, 5)), None);
+        assert_eq!(distance_map.distance(Coord::new(9, 9)), None);
+        assert_eq!(settled.last(), Some(&5));
+        assert_eq!(settled.len(), distance_map.reachable_count());
+    }
+
+    #[test]
+    fn search_size_mismatch() {
+        let Test { world, goals } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        let mut search_context = SearchContext::new(Size::new(5, 5));
+        for &coord in &goals {
+            populate_context.add(coord);
+        }
+        populate_context.populate_approach(&world, 20, &mut distance_map);
+        let mut path = Path::default();
+        search_context.search_path(&world, Coord::new(7, 7), 100, &distance_map, &mut path);
+        assert!(path.is_empty());
+        assert_eq!(
+            search_context.search_first(&world, Coord::new(7, 7), 100, &distance_map),
+            None
+        );
+    }
+
+    #[test]
+    fn chase_field() {
+        let Test { world, .. } = Test::from_str_slice(GRID_A);
+        let mut chase_field = ChaseField::new(world.grid.size(), 20);
+        assert_eq!(chase_field.next_step(Coord::new(4, 2)), None);
+        chase_field.update(&world, Coord::new(4, 8));
+        assert_eq!(chase_field.next_step(Coord::new(4, 2)), Some(CardinalDirection::South));
+        assert_eq!(chase_field.next_step(Coord::new(4, 8)), None);
+        let count = chase_field.distance_map.count;
+        chase_field.update(&world, Coord::new(4, 8));
+        assert_eq!(chase_field.distance_map.count, count);
+        chase_field.update(&world, Coord::new(5, 8));
+        assert_eq!(chase_field.goal(), Some(Coord::new(5, 8)));
+        assert_eq!(chase_field.next_step(Coord::new(4, 8)), Some(CardinalDirection::East));
+        assert_eq!(chase_field.next_step(Coord::new(4, 2)), Some(CardinalDirection::South));
+        chase_field.invalidate();
+        assert_eq!(chase_field.next_step(Coord::new(4, 8)), None);
+    }
+
+    #[test]
+    fn approach_diff() {
+        let Test { world, .. } = Test::from_str_slice(GRID_B);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        let mut diff = Vec::new();
+        populate_context.add(Coord::new(1, 1));
+        populate_context.add(Coord::new(8, 8));
+        populate_context.populate_approach_diff(&world, 20, &mut distance_map, &mut diff);
+        assert_eq!(diff.len(), 100);
+        let previous = distance_map.clone();
+        populate_context.add(Coord::new(1, 1));
+        populate_context.add(Coord::new(8, 7));
+        populate_context.populate_approach_diff(&world, 20, &mut distance_map, &mut diff);
+        let expected = world
+            .grid
+            .coord_iter()
+            .filter(|&coord| previous.distance(coord) != distance_map.distance(coord))
+            .collect::<Vec<_>>();
+        assert_eq!(diff, expected);
+        assert!(!diff.is_empty());
+        assert!(!diff.contains(&Coord::new(1, 1)));
+        assert!(!diff.contains(&Coord::new(3, 3)));
+        assert!(diff.contains(&Coord::new(8, 9)));
+    }
+
     const GRID_C: &[&str] = &[
         "..........",
         "..........",
@@ -568,4 +1441,60 @@ mod test {
             Some(CardinalDirection::West)
         );
     }
+
+    #[test]
+    fn compact_generation_reset() {
+        assert!(std::mem::size_of::<super::Cell<u32>>() < std::mem::size_of::<super::Cell<u64>>());
+        let Test { world, goals } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = CompactDistanceMap::with_generation(world.grid.size());
+        distance_map.count = u32::MAX - 3;
+        for _ in 0..4 {
+            for &coord in &goals {
+                populate_context.add(coord);
+            }
+            populate_context.populate_approach(&world, 7, &mut distance_map);
+            assert_eq!(distance_map.distance(Coord::new(4, 6)), Some(5));
+            assert_eq!(distance_map.distance(Coord::new(4, 3)), None);
+            for &coord in &goals {
+                populate_context.add(coord);
+            }
+            populate_context.populate_flee(&world, 10, &mut distance_map);
+            assert_eq!(distance_map.distance(Coord::new(4, 6)), Some(5));
+            assert_eq!(distance_map.distance(Coord::new(9, 7)), Some(11));
+            assert_eq!(distance_map.distance(Coord::new(1, 8)), Some(10));
+        }
+        assert!(distance_map.count < 100);
+    }
+
+    #[test]
+    fn frontier() {
+        let Test { world, .. } = Test::from_str_slice(GRID_A);
+        let mut populate_context = PopulateContext::default();
+        let mut distance_map = DistanceMap::new(world.grid.size());
+        let mut frontier = Vec::new();
+        populate_context.add(Coord::new(5, 2));
+        populate_context.populate_approach_frontier(&world, 2, &mut distance_map, &mut frontier);
+        frontier.sort_by_key(|coord| (coord.y, coord.x));
+        assert_eq!(
+            frontier,
+            vec![
+                Coord::new(5, 0),
+                Coord::new(4, 1),
+                Coord::new(6, 1),
+                Coord::new(3, 2),
+                Coord::new(7, 2),
+                Coord::new(4, 3),
+                Coord::new(6, 3),
+                Coord::new(5, 4),
+            ]
+        );
+        // cells whose only unpopulated neighbours are solid or outside the map aren't frontier cells
+        populate_context.add(Coord::new(1, 8));
+        populate_context.populate_approach_frontier(&world, 2, &mut distance_map, &mut frontier);
+        assert_eq!(distance_map.distance(Coord::new(0, 7)), Some(2));
+        assert!(!frontier.contains(&Coord::new(0, 7)));
+        assert!(frontier.contains(&Coord::new(3, 8)));
+        assert!(frontier.iter().all(|&coord| distance_map.distance(coord) == Some(2)));
+    }
 }
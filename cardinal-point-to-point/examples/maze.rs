@@ -9,10 +9,7 @@ struct Search<'a> {
 
 impl<'a> CanEnter for Search<'a> {
     fn can_enter(&self, coord: Coord) -> bool {
-        match self.maze.get(coord) {
-            Some(MazeCell::Passage) => true,
-            _ => false,
-        }
+        matches!(self.maze.get(coord), Some(MazeCell::Passage))
     }
 }
 
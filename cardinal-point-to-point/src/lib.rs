@@ -1,6 +1,11 @@
 pub use coord_2d::{Coord, Size};
 pub use direction::CardinalDirection;
-pub use grid_search_cardinal_common::{can_enter::CanEnter, coord::UnitCoord, path::Path, step::Step};
+pub use grid_search_cardinal_common::{
+    can_enter::{Bordered, CanEnter, Clearance},
+    coord::UnitCoord,
+    path::{self, Path, PathCursor},
+    step::Step,
+};
 use grid_search_cardinal_common::{
     coord::UNIT_COORDS,
     seen_set::{SeenSet, Visit},
@@ -9,7 +14,7 @@ use grid_search_cardinal_common::{
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
 
 #[derive(Debug)]
 struct Node {
@@ -18,6 +23,12 @@ struct Node {
     step: Step,
 }
 
+#[derive(Debug)]
+struct FloodNode {
+    cost: u32,
+    step: Step,
+}
+
 impl PartialEq for Node {
     fn eq(&self, other: &Self) -> bool {
         self.cost_plus_heuristic.eq(&other.cost_plus_heuristic)
@@ -26,10 +37,7 @@ impl PartialEq for Node {
 
 impl PartialOrd for Node {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match other.cost_plus_heuristic.partial_cmp(&self.cost_plus_heuristic) {
-            Some(Ordering::Equal) => self.cost.partial_cmp(&other.cost),
-            other => other,
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -44,28 +52,72 @@ impl Ord for Node {
     }
 }
 
-trait Profiler {
-    fn expand(&mut self);
-}
-
-impl Profiler for () {
-    fn expand(&mut self) {}
-}
-
 #[derive(Default, Debug)]
 pub struct Profile {
     expand: u64,
+    jump_scan: u64,
+    forced_neighbour: u64,
 }
 
-impl Profiler for Profile {
-    fn expand(&mut self) {
-        self.expand += 1;
+impl Profile {
+    /// The number of nodes taken from the open set and expanded.
+    pub fn expand(&self) -> u64 {
+        self.expand
+    }
+
+    /// The number of straight-line scans performed by `expand::JumpPoint`.
+    pub fn jump_scan(&self) -> u64 {
+        self.jump_scan
+    }
+
+    /// The number of forced neighbours found by `expand::JumpPoint`.
+    pub fn forced_neighbour(&self) -> u64 {
+        self.forced_neighbour
+    }
+
+    /// Combines the counts of two profiles, for aggregating over many searches.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            expand: self.expand + other.expand,
+            jump_scan: self.jump_scan + other.jump_scan,
+            forced_neighbour: self.forced_neighbour + other.forced_neighbour,
+        }
     }
 }
 
+mod profiler {
+    pub trait Profiler {
+        fn expand(&mut self);
+        fn jump_scan(&mut self);
+        fn forced_neighbour(&mut self);
+    }
+
+    impl Profiler for () {
+        fn expand(&mut self) {}
+        fn jump_scan(&mut self) {}
+        fn forced_neighbour(&mut self) {}
+    }
+
+    impl Profiler for super::Profile {
+        fn expand(&mut self) {
+            self.expand += 1;
+        }
+        fn jump_scan(&mut self) {
+            self.jump_scan += 1;
+        }
+        fn forced_neighbour(&mut self) {
+            self.forced_neighbour += 1;
+        }
+    }
+}
+
+use profiler::Profiler;
+
 pub struct Context {
     seen_set: SeenSet,
     priority_queue: BinaryHeap<Node>,
+    flood_queue: VecDeque<FloodNode>,
+    heuristic_weight: u32,
 }
 
 #[cfg(feature = "serialize")]
@@ -92,92 +144,102 @@ pub mod expand {
     #[derive(Debug, Clone, Copy)]
     pub struct Sequential;
 
+    /// Chooses between the other expansion strategies at runtime, e.g. from configuration.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Dynamic {
+        JumpPoint,
+        Sequential,
+    }
+
     impl Expand for JumpPoint {}
     impl Expand for Sequential {}
+    impl Expand for Dynamic {}
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct NoPath;
 
 mod private_expand {
-    use super::{expand, CanEnter, Context, Coord, Step};
+    use super::{expand, CanEnter, Context, Coord, Profiler, Step};
     pub struct Stop;
-    pub trait PrivateExpand {
-        fn consider<P: CanEnter>(
+    pub trait PrivateExpand: Copy {
+        fn consider<P: CanEnter, R: Profiler>(
+            self,
             context: &mut Context,
             point_to_point_search: &P,
             step: Step,
             cost: u32,
             goal: Coord,
+            profiler: &mut R,
         ) -> Option<Stop>;
-        fn expand<P: CanEnter>(
-            context: &mut Context,
-            point_to_point_search: &P,
-            step: Step,
-            cost: u32,
-            goal: Coord,
-        ) -> Option<Stop>;
-    }
-
-    impl PrivateExpand for expand::JumpPoint {
-        fn consider<P: CanEnter>(
+        fn expand<P: CanEnter, R: Profiler>(
+            self,
             context: &mut Context,
             point_to_point_search: &P,
             step: Step,
             cost: u32,
             goal: Coord,
+            profiler: &mut R,
         ) -> Option<Stop> {
-            context.consider_jps(point_to_point_search, step, cost, goal)
+            if let Some(Stop) = self.consider(context, point_to_point_search, step.forward(), cost, goal, profiler) {
+                return Some(Stop);
+            }
+            if let Some(Stop) = self.consider(context, point_to_point_search, step.left(), cost, goal, profiler) {
+                return Some(Stop);
+            }
+            if let Some(Stop) = self.consider(context, point_to_point_search, step.right(), cost, goal, profiler) {
+                return Some(Stop);
+            }
+            None
         }
+    }
 
-        fn expand<P: CanEnter>(
+    impl PrivateExpand for expand::JumpPoint {
+        fn consider<P: CanEnter, R: Profiler>(
+            self,
             context: &mut Context,
             point_to_point_search: &P,
             step: Step,
             cost: u32,
             goal: Coord,
+            profiler: &mut R,
         ) -> Option<Stop> {
-            if let Some(Stop) = Self::consider(context, point_to_point_search, step.forward(), cost, goal) {
-                return Some(Stop);
-            }
-            if let Some(Stop) = Self::consider(context, point_to_point_search, step.left(), cost, goal) {
-                return Some(Stop);
-            }
-            if let Some(Stop) = Self::consider(context, point_to_point_search, step.right(), cost, goal) {
-                return Some(Stop);
-            }
-            None
+            context.consider_jps(point_to_point_search, step, cost, goal, profiler)
         }
     }
 
     impl PrivateExpand for expand::Sequential {
-        fn consider<P: CanEnter>(
+        fn consider<P: CanEnter, R: Profiler>(
+            self,
             context: &mut Context,
             point_to_point_search: &P,
             step: Step,
             cost: u32,
             goal: Coord,
+            _profiler: &mut R,
         ) -> Option<Stop> {
             context.consider(point_to_point_search, step, cost, goal)
         }
+    }
 
-        fn expand<P: CanEnter>(
+    impl PrivateExpand for expand::Dynamic {
+        fn consider<P: CanEnter, R: Profiler>(
+            self,
             context: &mut Context,
             point_to_point_search: &P,
             step: Step,
             cost: u32,
             goal: Coord,
+            profiler: &mut R,
         ) -> Option<Stop> {
-            if let Some(Stop) = Self::consider(context, point_to_point_search, step.forward(), cost, goal) {
-                return Some(Stop);
-            }
-            if let Some(Stop) = Self::consider(context, point_to_point_search, step.left(), cost, goal) {
-                return Some(Stop);
-            }
-            if let Some(Stop) = Self::consider(context, point_to_point_search, step.right(), cost, goal) {
-                return Some(Stop);
+            match self {
+                Self::JumpPoint => {
+                    expand::JumpPoint.consider(context, point_to_point_search, step, cost, goal, profiler)
+                }
+                Self::Sequential => {
+                    expand::Sequential.consider(context, point_to_point_search, step, cost, goal, profiler)
+                }
             }
-            None
         }
     }
 }
@@ -190,9 +252,68 @@ impl Context {
         Self {
             seen_set: SeenSet::new(size),
             priority_queue: BinaryHeap::new(),
+            flood_queue: VecDeque::new(),
+            heuristic_weight: 1,
         }
     }
 
+    /// Multiplies the heuristic by `heuristic_weight`, which defaults to 1. Weights above 1 make
+    /// the search greedier, so it expands fewer cells, but the path it finds may be up to
+    /// `heuristic_weight` times longer than the shortest path. The weighted heuristic saturates at
+    /// `u32::MAX`, so very large weights degrade to a greedy search rather than overflowing. The
+    /// weight isn't serialized.
+    pub fn set_heuristic_weight(&mut self, heuristic_weight: u32) {
+        self.heuristic_weight = heuristic_weight;
+    }
+
+    pub fn heuristic_weight(&self) -> u32 {
+        self.heuristic_weight
+    }
+
+    fn heuristic(&self, coord: Coord, goal: Coord) -> u32 {
+        coord.manhattan_distance(goal).saturating_mul(self.heuristic_weight)
+    }
+
+    /// Incremented at the start of each search. Callers can tag their own per-cell data with
+    /// this value to tell whether it was written during the most recent search.
+    pub fn generation(&self) -> u64 {
+        self.seen_set.generation()
+    }
+
+    /// The number of searches that can run before the generation wraps, at which point every
+    /// cell is reset.
+    pub fn generation_remaining(&self) -> u64 {
+        self.seen_set.generation_remaining()
+    }
+
+    /// True if the cell was reached by the most recent search. When searching with
+    /// `expand::JumpPoint`, cells passed over between jump points are not recorded.
+    pub fn was_seen(&self, coord: Coord) -> bool {
+        self.seen_set.was_seen(coord)
+    }
+
+    /// The direction in which the most recent search entered the cell, or `None` if the cell
+    /// wasn't seen or is the start.
+    pub fn in_direction(&self, coord: Coord) -> Option<CardinalDirection> {
+        self.seen_set.in_direction(coord)
+    }
+
+    /// The cost of the path to `goal` found by the most recent search, or `None` if that search
+    /// didn't reach `goal`.
+    pub fn last_goal_cost(&self, goal: Coord) -> Option<u32> {
+        self.seen_set.cost(goal)
+    }
+
+    /// The cells left in the open set when the most recent search stopped, with the cost at
+    /// which each was reached, in no particular order.
+    #[cfg(feature = "debug-inspect")]
+    pub fn open_set_snapshot(&self) -> Vec<(Coord, u32)> {
+        self.priority_queue
+            .iter()
+            .map(|node| (node.step.to_coord, node.cost))
+            .collect()
+    }
+
     fn consider<P: CanEnter>(&mut self, point_to_point_search: &P, step: Step, cost: u32, goal: Coord) -> Option<Stop> {
         let cost = cost + 1;
         if let Some(Visit) = self.seen_set.try_visit_step(step, cost) {
@@ -200,8 +321,8 @@ impl Context {
                 return Some(Stop);
             }
             if point_to_point_search.can_step(step) {
-                let heuristic = step.to_coord.manhattan_distance(goal);
-                let cost_plus_heuristic = cost + heuristic;
+                let heuristic = self.heuristic(step.to_coord, goal);
+                let cost_plus_heuristic = cost.saturating_add(heuristic);
                 let node = Node {
                     cost,
                     cost_plus_heuristic,
@@ -213,13 +334,31 @@ impl Context {
         None
     }
 
-    fn consider_jps<P: CanEnter>(
+    fn consider_flood<P: CanEnter>(
+        &mut self,
+        point_to_point_search: &P,
+        step: Step,
+        cost: u32,
+        goals_remaining: &mut HashSet<Coord>,
+    ) {
+        let cost = cost + 1;
+        if let Some(Visit) = self.seen_set.try_visit_step(step, cost) {
+            goals_remaining.remove(&step.to_coord);
+            if point_to_point_search.can_step(step) {
+                self.flood_queue.push_back(FloodNode { cost, step });
+            }
+        }
+    }
+
+    fn consider_jps<P: CanEnter, R: Profiler>(
         &mut self,
         point_to_point_search: &P,
         mut step: Step,
         cost: u32,
         goal: Coord,
+        profiler: &mut R,
     ) -> Option<Stop> {
+        profiler.jump_scan();
         let mut jump_cost = 1;
         'outer: loop {
             if step.to_coord == goal {
@@ -234,6 +373,7 @@ impl Context {
                 return None;
             }
             if has_forced_neighbour(point_to_point_search, step, goal) {
+                profiler.forced_neighbour();
                 break;
             }
             // explore to the left only
@@ -258,6 +398,7 @@ impl Context {
                     break 'inner;
                 }
                 if has_forced_neighbour(point_to_point_search, side_step, goal) {
+                    profiler.forced_neighbour();
                     let jump_to_side_jump_point = Jump {
                         in_direction: side_step.in_direction.scale(side_jump_cost),
                         to_coord: side_step.to_coord,
@@ -266,11 +407,11 @@ impl Context {
                         .seen_set
                         .try_visit_jump(jump_to_side_jump_point, cost + jump_cost + side_jump_cost)
                     {
-                        let heuristic = side_step.to_coord.manhattan_distance(goal);
+                        let heuristic = self.heuristic(side_step.to_coord, goal);
                         let cost = cost + jump_cost + side_jump_cost;
                         let node = Node {
                             cost,
-                            cost_plus_heuristic: cost + heuristic,
+                            cost_plus_heuristic: cost.saturating_add(heuristic),
                             step: side_step,
                         };
                         self.priority_queue.push(node);
@@ -286,10 +427,10 @@ impl Context {
         let jump = step.scale_back(jump_cost);
         let cost = cost + jump_cost;
         if let Some(Visit) = self.seen_set.try_visit_jump(jump, cost) {
-            let heuristic = step.to_coord.manhattan_distance(goal);
+            let heuristic = self.heuristic(step.to_coord, goal);
             let node = Node {
                 cost,
-                cost_plus_heuristic: cost + heuristic,
+                cost_plus_heuristic: cost.saturating_add(heuristic),
                 step,
             };
             self.priority_queue.push(node);
@@ -299,6 +440,7 @@ impl Context {
 
     fn point_to_point_search_core<S, E, P>(
         &mut self,
+        expand: E,
         point_to_point_search: &S,
         start: Coord,
         goal: Coord,
@@ -309,6 +451,9 @@ impl Context {
         E: Expand,
         P: Profiler,
     {
+        if !self.seen_set.size().is_valid(start) {
+            return Err(NoPath);
+        }
         self.seen_set.init(start);
         self.priority_queue.clear();
         if start == goal {
@@ -317,19 +462,22 @@ impl Context {
         for &in_direction in &UNIT_COORDS {
             let to_coord = start + in_direction.to_coord();
             let step = Step { to_coord, in_direction };
-            if let Some(Stop) = E::consider(self, point_to_point_search, step, 1, goal) {
+            if let Some(Stop) = expand.consider(self, point_to_point_search, step, 0, goal, profiler) {
                 return Ok(());
             }
         }
         while let Some(Node { cost, step, .. }) = self.priority_queue.pop() {
             profiler.expand();
-            if let Some(Stop) = E::expand(self, point_to_point_search, step, cost, goal) {
+            if let Some(Stop) = expand.expand(self, point_to_point_search, step, cost, goal, profiler) {
                 return Ok(());
             }
         }
         Err(NoPath)
     }
 
+    /// Fills `path` with the steps from `start` to `goal`. If `start` and `goal` are the same,
+    /// `path` is left empty; use `Path::reaches` to tell that apart from a path that doesn't
+    /// lead to `goal`.
     pub fn point_to_point_search_path<S, E>(
         &mut self,
         expand: E,
@@ -342,9 +490,30 @@ impl Context {
         S: CanEnter,
         E: Expand,
     {
-        let _ = expand;
-        self.point_to_point_search_core::<_, E, _>(point_to_point_search, start, goal, &mut ())?;
+        self.point_to_point_search_core(expand, point_to_point_search, start, goal, &mut ())?;
+        self.seen_set.build_path_to(goal, path);
+        Ok(())
+    }
+
+    /// Like `point_to_point_search_path`, but also fills `waypoints` with the coords where the
+    /// path may change direction, ending with the goal. With `expand::JumpPoint` these are the
+    /// jump points; with `expand::Sequential` every cell on the path is a waypoint.
+    pub fn point_to_point_search_path_waypoints<S, E>(
+        &mut self,
+        expand: E,
+        point_to_point_search: &S,
+        start: Coord,
+        goal: Coord,
+        path: &mut Path,
+        waypoints: &mut Vec<Coord>,
+    ) -> Result<(), NoPath>
+    where
+        S: CanEnter,
+        E: Expand,
+    {
+        self.point_to_point_search_core(expand, point_to_point_search, start, goal, &mut ())?;
         self.seen_set.build_path_to(goal, path);
+        self.seen_set.build_waypoints_to(goal, waypoints);
         Ok(())
     }
 
@@ -359,11 +528,65 @@ impl Context {
         S: CanEnter,
         E: Expand,
     {
-        let _ = expand;
-        self.point_to_point_search_core::<_, E, _>(point_to_point_search, start, goal, &mut ())?;
+        self.point_to_point_search_core(expand, point_to_point_search, start, goal, &mut ())?;
         Ok(self.seen_set.first_direction_towards(goal))
     }
 
+    /// Returns the first step from `start` towards each of `goals`, in the same order, using a
+    /// single breadth-first flood from `start` that stops once every goal has been reached. An
+    /// entry is `None` if its goal is unreachable or equal to `start`. Each step begins a
+    /// shortest path to its goal, but where there are several shortest paths it may differ from
+    /// the step chosen by `point_to_point_search_first`.
+    #[must_use]
+    pub fn first_steps_to_many<S>(
+        &mut self,
+        point_to_point_search: &S,
+        start: Coord,
+        goals: &[Coord],
+    ) -> Vec<Option<CardinalDirection>>
+    where
+        S: CanEnter,
+    {
+        if !self.seen_set.size().is_valid(start) {
+            return vec![None; goals.len()];
+        }
+        let mut goals_remaining = goals
+            .iter()
+            .cloned()
+            .filter(|&goal| goal != start)
+            .collect::<HashSet<_>>();
+        self.seen_set.init(start);
+        self.flood_queue.clear();
+        for &in_direction in &UNIT_COORDS {
+            let to_coord = start + in_direction.to_coord();
+            self.consider_flood(
+                point_to_point_search,
+                Step { to_coord, in_direction },
+                0,
+                &mut goals_remaining,
+            );
+        }
+        while !goals_remaining.is_empty() {
+            let FloodNode { cost, step } = match self.flood_queue.pop_front() {
+                Some(node) => node,
+                None => break,
+            };
+            self.consider_flood(point_to_point_search, step.forward(), cost, &mut goals_remaining);
+            self.consider_flood(point_to_point_search, step.left(), cost, &mut goals_remaining);
+            self.consider_flood(point_to_point_search, step.right(), cost, &mut goals_remaining);
+        }
+        goals
+            .iter()
+            .map(|&goal| {
+                if goal != start && !goals_remaining.contains(&goal) {
+                    self.seen_set.first_direction_towards(goal)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     pub fn point_to_point_search_profile<S, E>(
         &mut self,
         expand: E,
@@ -375,9 +598,8 @@ impl Context {
         S: CanEnter,
         E: Expand,
     {
-        let _ = expand;
         let mut profile = Profile::default();
-        let result = self.point_to_point_search_core::<_, E, _>(point_to_point_search, start, goal, &mut profile);
+        let result = self.point_to_point_search_core(expand, point_to_point_search, start, goal, &mut profile);
         (profile, result)
     }
 }
@@ -493,6 +715,22 @@ mod test {
         }
     }
 
+    /// Forbids every eastward step, for checking that adaptors keep the wrapped type's edge rules.
+    struct NoEastSteps<'a> {
+        grid: &'a Grid<Cell>,
+    }
+
+    impl<'a> CanEnter for NoEastSteps<'a> {
+        fn can_enter(&self, coord: Coord) -> bool {
+            Search { grid: self.grid }.can_enter(coord)
+        }
+        fn can_step(&self, step: Step) -> bool {
+            step.in_direction.to_cardinal_direction() != CardinalDirection::East && self.can_enter(step.to_coord)
+        }
+    }
+
+    const GRID_OPEN: &[&str] = &["......", "......", ".@..*.", "......", "......"];
+
     fn test(grid_str_slice: &[&str], len: Option<usize>) {
         let Test { grid, start, goal } = str_slice_to_test(grid_str_slice);
         let mut ctx = Context::new(grid.size());
@@ -551,6 +789,469 @@ mod test {
         test(GRID_B, Some(22));
     }
 
+    #[test]
+    fn path_visits() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::JumpPoint, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        assert!(path.visits(goal));
+        assert!(path.visits(Coord::new(8, 4)));
+        assert!(!path.visits(Coord::new(0, 0)));
+        assert!(!path.visits(start));
+        assert_eq!(path.nth_coord(path.len() - 1), Some(goal));
+        assert_eq!(path.nth_coord(path.len()), None);
+        assert_eq!(path.nth_coord(0).unwrap().manhattan_distance(start), 1);
+    }
+
+    #[test]
+    fn path_into_iter() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::Sequential, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        let mut borrowed = Vec::new();
+        for node in &path {
+            borrowed.push(node.to_coord);
+        }
+        assert_eq!(borrowed.len(), path.len());
+        let owned = path.into_iter().map(|node| node.to_coord).collect::<Vec<_>>();
+        assert_eq!(owned, borrowed);
+        assert_eq!(owned.last(), Some(&goal));
+    }
+
+    #[test]
+    fn waypoints() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_K);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        let mut waypoints = Vec::new();
+        ctx.point_to_point_search_path_waypoints(
+            expand::JumpPoint,
+            &Search { grid: &grid },
+            start,
+            goal,
+            &mut path,
+            &mut waypoints,
+        )
+        .unwrap();
+        assert!(waypoints.len() < path.len());
+        assert_eq!(waypoints.last(), Some(&goal));
+        let mut coords = path.iter().map(|node| node.to_coord);
+        for waypoint in &waypoints {
+            assert!(coords.any(|coord| coord == *waypoint));
+        }
+    }
+
+    #[test]
+    fn last_goal_cost() {
+        let mut path = Path::default();
+        for &expand in &[expand::Dynamic::Sequential, expand::Dynamic::JumpPoint] {
+            let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+            let mut ctx = Context::new(grid.size());
+            ctx.point_to_point_search_path(expand, &Search { grid: &grid }, start, goal, &mut path)
+                .unwrap();
+            assert_eq!(ctx.last_goal_cost(goal), Some(path.len() as u32));
+            assert_eq!(ctx.last_goal_cost(start), Some(0));
+            let Test { grid, start, goal } = str_slice_to_test(GRID_I);
+            ctx.point_to_point_search_path(expand, &Search { grid: &grid }, start, goal, &mut path)
+                .unwrap_err();
+            assert_eq!(ctx.last_goal_cost(goal), None);
+        }
+    }
+
+    const GRID_JUNCTION: &[&str] = &["####.#####", "####.#####", "....@.....", "####.#####", "####.#####"];
+
+    #[test]
+    fn first_steps_to_many() {
+        let Test { grid, start, .. } = str_slice_to_test(GRID_JUNCTION);
+        let search = Search { grid: &grid };
+        let goals = [
+            Coord::new(0, 2),
+            Coord::new(9, 2),
+            Coord::new(4, 0),
+            Coord::new(0, 0),
+            Coord::new(3, 1),
+            start,
+        ];
+        let mut ctx = Context::new(grid.size());
+        let first_steps = ctx.first_steps_to_many(&search, start, &goals);
+        assert_eq!(
+            first_steps,
+            vec![
+                Some(CardinalDirection::West),
+                Some(CardinalDirection::East),
+                Some(CardinalDirection::North),
+                None,
+                Some(CardinalDirection::North),
+                None,
+            ]
+        );
+        for (&goal, &first_step) in goals.iter().zip(first_steps.iter()) {
+            if let Ok(individual) = ctx.point_to_point_search_first(expand::Sequential, &search, start, goal) {
+                assert_eq!(individual, first_step);
+            }
+        }
+    }
+
+    #[test]
+    fn first_steps_to_many_ties() {
+        let Test { grid, start, .. } = str_slice_to_test(GRID_OPEN);
+        let search = Search { grid: &grid };
+        let goals = [Coord::new(5, 0), Coord::new(5, 4), Coord::new(0, 0), Coord::new(3, 4)];
+        let mut ctx = Context::new(grid.size());
+        let first_steps = ctx.first_steps_to_many(&search, start, &goals);
+        let mut path = Path::default();
+        for (&goal, &first_step) in goals.iter().zip(first_steps.iter()) {
+            ctx.point_to_point_search_path(expand::Sequential, &search, start, goal, &mut path)
+                .unwrap();
+            let shortest = path.len();
+            let next = start + first_step.unwrap().coord();
+            ctx.point_to_point_search_path(expand::Sequential, &search, next, goal, &mut path)
+                .unwrap();
+            assert_eq!(path.len(), shortest - 1);
+        }
+        let nearby_goal = start + Coord::new(1, 0);
+        assert_eq!(
+            ctx.first_steps_to_many(&search, start, &[nearby_goal]),
+            vec![Some(CardinalDirection::East)]
+        );
+        assert!(!ctx.was_seen(Coord::new(5, 4)));
+    }
+
+    const GRID_NARROW: &[&str] = &[
+        "..........",
+        "..........",
+        "..........",
+        "#####.####",
+        "..........",
+        "..@.......",
+        "..........",
+        "#####.####",
+        "..........",
+        "......*...",
+        "..........",
+    ];
+
+    const GRID_WIDE: &[&str] = &[
+        "..........",
+        "..........",
+        "..........",
+        "#####..###",
+        "..........",
+        "..@.......",
+        "..........",
+        "#####..###",
+        "..........",
+        "......*...",
+        "..........",
+    ];
+
+    #[test]
+    fn clearance() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_NARROW);
+        let mut ctx = Context::new(grid.size());
+        let search = Search { grid: &grid };
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::Sequential, &search, start, goal, &mut path)
+            .unwrap();
+        ctx.point_to_point_search_path(expand::Sequential, &Clearance::new(&search), start, goal, &mut path)
+            .unwrap_err();
+        let Test { grid, start, goal } = str_slice_to_test(GRID_WIDE);
+        let search = Search { grid: &grid };
+        ctx.point_to_point_search_path(expand::Sequential, &Clearance::new(&search), start, goal, &mut path)
+            .unwrap();
+        assert!(path.iter().any(|node| node.to_coord.y == 7));
+    }
+
+    #[test]
+    fn clearance_keeps_can_step() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_OPEN);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        let search = Search { grid: &grid };
+        let no_east_steps = NoEastSteps { grid: &grid };
+        for &expand in &[expand::Dynamic::Sequential, expand::Dynamic::JumpPoint] {
+            ctx.point_to_point_search_path(expand, &Clearance::new(&search), start, goal, &mut path)
+                .unwrap();
+            assert_eq!(
+                ctx.point_to_point_search_path(expand, &no_east_steps, start, goal, &mut path),
+                Err(NoPath)
+            );
+            assert_eq!(
+                ctx.point_to_point_search_path(expand, &Clearance::new(&no_east_steps), start, goal, &mut path),
+                Err(NoPath)
+            );
+        }
+    }
+
+    #[test]
+    fn single_row() {
+        test(&["@..................*"], Some(19));
+        test(&["*..................@"], Some(19));
+        test(&["..@.......*........."], Some(8));
+        test(&["..@.......#.*......."], None);
+    }
+
+    #[test]
+    fn single_column() {
+        test(&["@", ".", ".", ".", ".", ".", "*"], Some(6));
+        test(&["*", ".", ".", ".", ".", ".", "@"], Some(6));
+        test(&[".", "@", ".", ".", "*", "."], Some(3));
+        test(&["@", ".", "#", ".", "*"], None);
+    }
+
+    #[test]
+    fn single_cell() {
+        test(&["@"], Some(0));
+        let Test { grid, start, goal } = str_slice_to_test(&["@"]);
+        let mut ctx = Context::new(grid.size());
+        for &expand in &[expand::Dynamic::Sequential, expand::Dynamic::JumpPoint] {
+            assert_eq!(
+                ctx.point_to_point_search_first(expand, &Search { grid: &grid }, start, goal),
+                Ok(None)
+            );
+        }
+    }
+
+    const GRID_SYMMETRIC: &[&str] = &[".....", ".@...", ".....", "...*.", "....."];
+
+    #[test]
+    fn symmetric_tie_break() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_SYMMETRIC);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        use CardinalDirection::*;
+        let expected = [
+            (expand::Dynamic::Sequential, [East, East, South, South]),
+            (expand::Dynamic::JumpPoint, [South, South, East, East]),
+        ];
+        for &(expand, directions) in &expected {
+            ctx.point_to_point_search_path(expand, &Search { grid: &grid }, start, goal, &mut path)
+                .unwrap();
+            assert_eq!(
+                path.iter().map(|node| node.in_direction).collect::<Vec<_>>(),
+                directions
+            );
+        }
+    }
+
+    #[test]
+    fn path_cursor() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::JumpPoint, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        let directions = path.iter().map(|node| node.in_direction).collect::<Vec<_>>();
+        let late_coord = path.nth_coord(15).unwrap();
+        let mut cursor = PathCursor::new(start, path);
+        assert_eq!(cursor.remaining(), 22);
+        assert_eq!(cursor.next_direction(), Some(directions[0]));
+        assert_eq!(cursor.next_direction(), Some(directions[1]));
+        assert_eq!(cursor.remaining(), 20);
+        assert!(cursor.resync(late_coord));
+        assert_eq!(cursor.remaining(), 6);
+        assert_eq!(cursor.next_direction(), Some(directions[16]));
+        assert!(!cursor.resync(Coord::new(9, 0)));
+        assert_eq!(cursor.remaining(), 5);
+        assert!(cursor.resync(start));
+        assert_eq!(cursor.remaining(), 22);
+        while cursor.next_direction().is_some() {}
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn path_to_edges() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::JumpPoint, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        let edges = path::to_edges(start, &path);
+        assert_eq!(edges.len(), path.len());
+        assert_eq!(edges[0].0, start);
+        assert_eq!(edges.last().unwrap().1, goal);
+        for (&(_, to), &(from, _)) in edges.iter().zip(edges.iter().skip(1)) {
+            assert_eq!(to, from);
+        }
+        for &(from, to) in &edges {
+            assert_eq!(from.manhattan_distance(to), 1);
+        }
+        path.clear();
+        assert!(path::to_edges(start, &path).is_empty());
+    }
+
+    #[test]
+    fn path_inflate() {
+        let Test { grid, start, .. } = str_slice_to_test(GRID_D);
+        let goal = start + Coord::new(2, 0);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::JumpPoint, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        let inflated = path::inflate(start, &path, 1);
+        assert_eq!(inflated.len(), 15);
+        for y in -1..=1 {
+            for x in -1..=3 {
+                assert!(inflated.contains(&(start + Coord::new(x, y))));
+            }
+        }
+        assert_eq!(path::inflate(start, &path, 0).len(), 3);
+    }
+
+    #[test]
+    fn path_inflate_extremes() {
+        let Test { grid, start, .. } = str_slice_to_test(GRID_D);
+        let goal = start + Coord::new(2, 0);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::JumpPoint, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        let radius = u8::MAX as usize;
+        let inflated = path::inflate(start, &path, u8::MAX);
+        assert_eq!(inflated.len(), (2 * radius + 3) * (2 * radius + 1));
+        assert!(inflated.contains(&start));
+        assert!(path.iter().all(|node| inflated.contains(&node.to_coord)));
+        let corner = Coord::new(i32::MAX, i32::MIN);
+        let inflated = path::inflate(corner, &Path::default(), 1);
+        assert_eq!(inflated.len(), 4);
+        assert!(inflated.contains(&corner));
+    }
+
+    #[test]
+    fn path_overlap() {
+        let Test { grid, .. } = str_slice_to_test(GRID_JUNCTION);
+        let search = Search { grid: &grid };
+        let mut ctx = Context::new(grid.size());
+        let mut path_a = Path::default();
+        let mut path_b = Path::default();
+        let (start_a, goal_a) = (Coord::new(0, 2), Coord::new(9, 2));
+        let (start_b, goal_b) = (Coord::new(4, 0), Coord::new(4, 4));
+        ctx.point_to_point_search_path(expand::JumpPoint, &search, start_a, goal_a, &mut path_a)
+            .unwrap();
+        ctx.point_to_point_search_path(expand::JumpPoint, &search, start_b, goal_b, &mut path_b)
+            .unwrap();
+        assert_eq!(
+            path::overlap(start_a, &path_a, start_b, &path_b),
+            vec![Coord::new(4, 2)]
+        );
+        assert_eq!(
+            path::overlap(start_a, &path_a, start_a, &path_a).len(),
+            path_a.len() + 1
+        );
+    }
+
+    #[cfg(feature = "debug-inspect")]
+    #[test]
+    fn open_set_snapshot() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::Sequential, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        let snapshot = ctx.open_set_snapshot();
+        assert!(!snapshot.is_empty());
+        for &(coord, cost) in &snapshot {
+            assert!(ctx.was_seen(coord));
+            assert!(!path.visits(coord));
+            assert!(cost <= path.len() as u32);
+            assert!(cost + coord.manhattan_distance(goal) >= path.len() as u32);
+        }
+    }
+
+    #[test]
+    fn bordered() {
+        let Test { grid, .. } = str_slice_to_test(GRID_D);
+        let search = Search { grid: &grid };
+        let bordered = Bordered::new(&search, grid.size(), 1);
+        for coord in grid.size().coord_iter_row_major() {
+            let on_edge = coord.x == 0 || coord.y == 0 || coord.x == 9 || coord.y == 9;
+            assert_eq!(bordered.can_enter(coord), !on_edge);
+        }
+        assert!(!bordered.can_enter(Coord::new(-1, 5)));
+        let (start, goal) = (Coord::new(1, 1), Coord::new(8, 8));
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::JumpPoint, &bordered, start, goal, &mut path)
+            .unwrap();
+        assert_eq!(path.len(), 14);
+        for node in path.iter() {
+            assert!(bordered.can_enter(node.to_coord));
+        }
+    }
+
+    #[test]
+    fn bordered_keeps_can_step() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_OPEN);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        let search = Search { grid: &grid };
+        let no_east_steps = NoEastSteps { grid: &grid };
+        for &expand in &[expand::Dynamic::Sequential, expand::Dynamic::JumpPoint] {
+            for &border in &[0, 1] {
+                ctx.point_to_point_search_path(
+                    expand,
+                    &Bordered::new(&search, grid.size(), border),
+                    start,
+                    goal,
+                    &mut path,
+                )
+                .unwrap();
+                assert_eq!(
+                    ctx.point_to_point_search_path(
+                        expand,
+                        &Bordered::new(&no_east_steps, grid.size(), border),
+                        start,
+                        goal,
+                        &mut path
+                    ),
+                    Err(NoPath)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bordered_extremes() {
+        let Test { grid, .. } = str_slice_to_test(GRID_D);
+        let search = Search { grid: &grid };
+        let bordered = Bordered::new(&search, grid.size(), u32::MAX);
+        for coord in grid.size().coord_iter_row_major() {
+            assert!(!bordered.can_enter(coord));
+        }
+        let bordered = Bordered::new(&search, grid.size(), 1);
+        for &coord in &[
+            Coord::new(-1, 5),
+            Coord::new(i32::MIN, 0),
+            Coord::new(0, i32::MIN),
+            Coord::new(i32::MAX, 5),
+            Coord::new(5, i32::MAX),
+        ] {
+            assert!(!bordered.can_enter(coord));
+        }
+    }
+
+    #[test]
+    fn seen_after_search() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::Sequential, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        assert!(ctx.was_seen(start));
+        assert_eq!(ctx.in_direction(start), None);
+        for node in path.iter() {
+            assert!(ctx.was_seen(node.to_coord));
+            assert_eq!(ctx.in_direction(node.to_coord), Some(node.in_direction));
+        }
+        assert!(!ctx.was_seen(Coord::new(0, 0)));
+        assert_eq!(ctx.in_direction(Coord::new(0, 0)), None);
+        assert!(!ctx.was_seen(Coord::new(-1, 0)));
+    }
+
     const GRID_C: &[&str] = &[
         "..........",
         "..........",
@@ -587,6 +1288,60 @@ mod test {
         test(GRID_D, Some(0));
     }
 
+    #[test]
+    fn heuristic_weight() {
+        for &expand in &[expand::Dynamic::Sequential, expand::Dynamic::JumpPoint] {
+            let Test { grid, start, goal } = str_slice_to_test(GRID_M);
+            let mut ctx = Context::new(grid.size());
+            let (optimal_profile, result) =
+                ctx.point_to_point_search_profile(expand, &Search { grid: &grid }, start, goal);
+            assert_eq!(result, Ok(()));
+            ctx.set_heuristic_weight(3);
+            let (weighted_profile, result) =
+                ctx.point_to_point_search_profile(expand, &Search { grid: &grid }, start, goal);
+            assert_eq!(result, Ok(()));
+            assert!(weighted_profile.expand() < optimal_profile.expand());
+            let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+            let mut path = Path::default();
+            ctx.point_to_point_search_path(expand, &Search { grid: &grid }, start, goal, &mut path)
+                .unwrap();
+            assert!(path.reaches(start, goal));
+            assert!(path.len() >= 22);
+            assert!(path.len() <= 3 * 22);
+        }
+    }
+
+    #[test]
+    fn heuristic_weight_saturates() {
+        for &expand in &[expand::Dynamic::Sequential, expand::Dynamic::JumpPoint] {
+            let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+            let mut ctx = Context::new(grid.size());
+            ctx.set_heuristic_weight(u32::MAX);
+            let mut path = Path::default();
+            ctx.point_to_point_search_path(expand, &Search { grid: &grid }, start, goal, &mut path)
+                .unwrap();
+            assert!(path.reaches(start, goal));
+        }
+    }
+
+    #[test]
+    fn path_reaches() {
+        let Test { grid, start, .. } = str_slice_to_test(GRID_D);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::JumpPoint, &Search { grid: &grid }, start, start, &mut path)
+            .unwrap();
+        assert!(path.is_empty());
+        assert!(path.reaches(start, start));
+        let goal = Coord::new(7, 2);
+        assert!(!path.reaches(start, goal));
+        ctx.point_to_point_search_path(expand::JumpPoint, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        assert!(path.reaches(start, goal));
+        assert!(!path.reaches(start, start));
+        assert!(!path.reaches(Coord::new(0, 0), goal));
+    }
+
     const GRID_E: &[&str] = &[
         "..........",
         "..........",
@@ -605,6 +1360,18 @@ mod test {
         test(GRID_E, Some(2));
     }
 
+    #[test]
+    fn path_bounding_box() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_E);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        ctx.point_to_point_search_path(expand::Sequential, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        assert_eq!(path::bounding_box(start, &path), (Coord::new(1, 7), Coord::new(2, 8)));
+        path.clear();
+        assert_eq!(path::bounding_box(start, &path), (start, start));
+    }
+
     const GRID_F: &[&str] = &[
         "..........",
         ".......#.#",
@@ -785,6 +1552,64 @@ mod test {
         test(GRID_O, Some(18));
     }
 
+    #[test]
+    fn generation() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        let generation = ctx.generation();
+        ctx.point_to_point_search_path(expand::Sequential, &Search { grid: &grid }, start, goal, &mut path)
+            .unwrap();
+        assert_eq!(ctx.generation(), generation + 1);
+        ctx.point_to_point_search_first(expand::JumpPoint, &Search { grid: &grid }, start, goal)
+            .unwrap();
+        assert_eq!(ctx.generation(), generation + 2);
+    }
+
+    #[test]
+    fn start_outside_grid() {
+        let grid = Grid::new_clone(Size::new(0, 0), Cell::Traversable);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        assert_eq!(
+            ctx.point_to_point_search_path(
+                expand::Sequential,
+                &Search { grid: &grid },
+                Coord::new(0, 0),
+                Coord::new(1, 1),
+                &mut path
+            ),
+            Err(NoPath)
+        );
+        assert_eq!(
+            ctx.point_to_point_search_first(
+                expand::JumpPoint,
+                &Search { grid: &grid },
+                Coord::new(0, 0),
+                Coord::new(0, 0)
+            ),
+            Err(NoPath)
+        );
+    }
+
+    #[test]
+    fn dynamic_expand() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_K);
+        let mut ctx = Context::new(grid.size());
+        let mut path = Path::default();
+        for &expand in &[expand::Dynamic::Sequential, expand::Dynamic::JumpPoint] {
+            ctx.point_to_point_search_path(expand, &Search { grid: &grid }, start, goal, &mut path)
+                .unwrap();
+            assert_eq!(path.len(), 32);
+        }
+        let (profile, _) =
+            ctx.point_to_point_search_profile(expand::Dynamic::Sequential, &Search { grid: &grid }, start, goal);
+        assert_eq!(profile.jump_scan(), 0);
+        let (profile, _) =
+            ctx.point_to_point_search_profile(expand::Dynamic::JumpPoint, &Search { grid: &grid }, start, goal);
+        assert!(profile.jump_scan() > 0);
+    }
+
     #[test]
     fn grid_random() {
         let mut rng = Isaac64Rng::seed_from_u64(0);
@@ -808,4 +1633,37 @@ mod test {
             assert_eq!(seq_len, jps_len);
         }
     }
+
+    #[test]
+    fn profile_jump_point() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let (profile, result) =
+            ctx.point_to_point_search_profile(expand::JumpPoint, &Search { grid: &grid }, start, goal);
+        assert_eq!(result, Ok(()));
+        assert!(profile.jump_scan() > 0);
+        assert!(profile.forced_neighbour() > 0);
+        let (profile, result) =
+            ctx.point_to_point_search_profile(expand::JumpPoint, &Search { grid: &grid }, start, start);
+        assert_eq!(result, Ok(()));
+        assert_eq!(profile.jump_scan(), 0);
+        assert_eq!(profile.forced_neighbour(), 0);
+    }
+
+    #[test]
+    fn profile_merge() {
+        let Test { grid, start, goal } = str_slice_to_test(GRID_B);
+        let mut ctx = Context::new(grid.size());
+        let mut total = Profile::default();
+        let mut expand = 0;
+        for _ in 0..3 {
+            let (profile, result) =
+                ctx.point_to_point_search_profile(expand::JumpPoint, &Search { grid: &grid }, start, goal);
+            assert_eq!(result, Ok(()));
+            expand += profile.expand();
+            total = total.merge(profile);
+        }
+        assert!(expand > 0);
+        assert_eq!(total.expand(), expand);
+    }
 }